@@ -0,0 +1,100 @@
+use crate::math::Rect;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Role
+{
+    Group,
+    Button,
+    CheckBox,
+    Slider,
+    TextField,
+    Label,
+}
+
+#[derive(Clone)]
+pub struct Node
+{
+    pub role: Role,
+    pub name: String,
+    pub value: Option<String>,
+    //absolute, in ui units
+    pub bounds: Rect,
+    pub focused: bool,
+    pub checked: Option<bool>,
+    //index into the node list, parents always come before their children
+    pub parent: Option<usize>,
+}
+
+pub(crate) struct AccessTree
+{
+    enabled: bool,
+    nodes: Vec<Node>,
+    //open groups while painting
+    stack: Vec<usize>,
+}
+
+impl AccessTree
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { enabled: false, nodes: Vec::new(), stack: Vec::new() }
+    }
+
+    #[inline] pub(crate) fn enabled(&self) -> bool { self.enabled }
+    #[inline] pub(crate) fn nodes(&self) -> &[Node] { &self.nodes }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool)
+    {
+        self.enabled = enabled;
+        if !enabled { self.nodes.clear(); }
+    }
+
+    pub(crate) fn clear(&mut self)
+    {
+        self.nodes.clear();
+        self.stack.clear();
+    }
+
+    fn add(&mut self, role: Role, bounds: Rect, focused: bool) -> usize
+    {
+        let parent = self.stack.last().copied();
+        self.nodes.push(Node { role, name: String::new(), value: None, bounds, focused, checked: None, parent });
+        self.nodes.len() - 1
+    }
+
+    pub(crate) fn push(&mut self, role: Role, bounds: Rect, focused: bool)
+    {
+        if !self.enabled { return; }
+        let index = self.add(role, bounds, focused);
+        self.stack.push(index);
+    }
+
+    pub(crate) fn pop(&mut self)
+    {
+        if !self.enabled { return; }
+        self.stack.pop();
+    }
+
+    //a button directly around a leaf takes over its meaning, e.g. a check in a response becomes one checkbox
+    pub(crate) fn leaf(&mut self, role: Role, name: &str, value: Option<String>, checked: Option<bool>, bounds: Rect, focused: bool)
+    {
+        if !self.enabled { return; }
+        if let Some(&parent) = self.stack.last()
+        {
+            let parent = &mut self.nodes[parent];
+            if parent.role == Role::Button || (parent.role != Role::Group && role == Role::Label)
+            {
+                if role != Role::Label { parent.role = role; }
+                if parent.name.is_empty() { parent.name = name.to_string(); }
+                if value.is_some() { parent.value = value; }
+                if checked.is_some() { parent.checked = checked; }
+                return;
+            }
+        }
+        let index = self.add(role, bounds, focused);
+        let node = &mut self.nodes[index];
+        node.name = name.to_string();
+        node.value = value;
+        node.checked = checked;
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{math::Vec2, paint::Color};
+
+pub trait Lerp: Copy
+{
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32
+{
+    #[inline]
+    fn lerp(self, other: Self, t: f32) -> Self
+    {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for Vec2
+{
+    #[inline]
+    fn lerp(self, other: Self, t: f32) -> Self
+    {
+        Vec2(self.0.lerp(other.0, t), self.1.lerp(other.1, t))
+    }
+}
+
+impl Lerp for Color
+{
+    #[inline]
+    fn lerp(self, other: Self, t: f32) -> Self
+    {
+        Color { r: self.r.lerp(other.r, t), g: self.g.lerp(other.g, t), b: self.b.lerp(other.b, t), a: self.a.lerp(other.a, t) }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Easing
+{
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing
+{
+    pub fn apply(self, t: f32) -> f32
+    {
+        let t = t.clamp(0.0, 1.0);
+        match self
+        {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Tween<V: Lerp>
+{
+    from: V,
+    to: V,
+    duration: f32,
+    elapsed: f32,
+    easing: Easing,
+}
+
+impl<V: Lerp> Tween<V>
+{
+    pub fn new(value: V) -> Self
+    {
+        Self { from: value, to: value, duration: 0.0, elapsed: 0.0, easing: Easing::EaseInOut }
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self
+    {
+        self.easing = easing;
+        self
+    }
+
+    //continues from the current value, even if the last transition is still running
+    pub fn start(&mut self, to: V, duration: f32)
+    {
+        self.from = self.value();
+        self.to = to;
+        self.duration = duration;
+        self.elapsed = 0.0;
+    }
+
+    pub fn set(&mut self, value: V)
+    {
+        self.from = value;
+        self.to = value;
+        self.duration = 0.0;
+        self.elapsed = 0.0;
+    }
+
+    //returns whether the tween is still running afterwards
+    pub fn advance(&mut self, dt: f32) -> bool
+    {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        self.running()
+    }
+
+    #[inline] pub fn running(&self) -> bool { self.elapsed < self.duration }
+    #[inline] pub fn target(&self) -> V { self.to }
+
+    pub fn value(&self) -> V
+    {
+        if !self.running() { return self.to; }
+        self.from.lerp(self.to, self.easing.apply(self.elapsed / self.duration))
+    }
+}
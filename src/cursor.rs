@@ -0,0 +1,35 @@
+use std::any::Any;
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum CursorIcon
+{
+    #[default]
+    Default,
+    Pointer,
+    Text,
+    Grab,
+    Grabbing,
+    Move,
+    ResizeHorizontal,
+    ResizeVertical,
+    NotAllowed,
+    Hidden,
+}
+
+pub(crate) struct Pointer
+{
+    //reset before every pointer event, the widgets under the pointer set it again
+    pub(crate) cursor: CursorIcon,
+    //stays until released, e.g. while dragging a camera with raw mouse deltas
+    pub(crate) grab: bool,
+    //payload of the running drag and drop, gone after the release went through the tree
+    pub(crate) drag: Option<Box<dyn Any>>,
+}
+
+impl Pointer
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { cursor: CursorIcon::Default, grab: false, drag: None }
+    }
+}
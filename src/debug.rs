@@ -0,0 +1,70 @@
+use crate::{math::{Vec2, Rect}, text::Align, paint::{Painter, Color}, hit::Registry, Misfit};
+
+const OUTLINE_WIDTH: f32 = 0.05;
+const TEXT_SIZE: f32 = 0.5;
+
+pub(crate) struct Debug
+{
+    enabled: bool,
+    //absolute origin of the widgets behind the misfits of the last layout, by misfit index
+    located: Vec<(usize, Vec2)>,
+}
+
+impl Debug
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { enabled: false, located: Vec::new() }
+    }
+
+    #[inline] pub(crate) fn enabled(&self) -> bool { self.enabled }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool)
+    {
+        self.enabled = enabled;
+    }
+
+    pub(crate) fn clear(&mut self)
+    {
+        self.located.clear();
+    }
+
+    pub(crate) fn locate(&mut self, misfit: usize, origin: Vec2)
+    {
+        if self.enabled { self.located.push((misfit, origin)); }
+    }
+
+    //on top of everything else
+    pub(crate) fn paint(&self, painter: &mut Painter, hits: &Registry, misfits: &[Misfit])
+    {
+        if !self.enabled { return; }
+        let outline = Color::from_discrete_srgb(50, 150, 250, 255);
+        let red = Color::from_discrete_srgb(250, 0, 0, 255);
+        painter.push_top_layer(Vec2::zero());
+        for hit in hits.hits() { painter.draw_outline(hit.rect, OUTLINE_WIDTH, outline); }
+        //misfits of widgets that never said where they are get stacked in the corner
+        let mut unlocated = 0;
+        for (i, misfit) in misfits.iter().enumerate()
+        {
+            let label = format!("{} min {:.1}x{:.1} > {:.1}x{:.1}", misfit.name, misfit.requested.0, misfit.requested.1, misfit.available.0, misfit.available.1);
+            let width = painter.text_width(&label, TEXT_SIZE);
+            let origin = match self.located.iter().find(|(index, _)| *index == i)
+            {
+                Some((_, origin)) =>
+                {
+                    painter.draw_outline(Rect::new_size(*origin, misfit.available), 2.0 * OUTLINE_WIDTH, red);
+                    *origin
+                },
+                None =>
+                {
+                    unlocated += 1;
+                    Vec2(0.0, (unlocated - 1) as f32 * TEXT_SIZE)
+                }
+            };
+            let label_rect = Rect::new_size(origin, Vec2(width, TEXT_SIZE));
+            painter.draw_rect(label_rect, red);
+            painter.draw_text(label_rect, &label, TEXT_SIZE, Align::Left, false, Color::from_discrete_srgb(255, 255, 255, 255));
+        }
+        painter.pop_layer();
+    }
+}
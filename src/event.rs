@@ -1,8 +1,9 @@
 use std::fmt::{self, Display, Formatter};
 
-use crate::math::Vec2;
+use crate::{math::Vec2, timer::TimerId, gesture::Gesture};
 
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton
 {
     Primary,
@@ -11,6 +12,7 @@ pub enum MouseButton
 }
 
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key
 {
     Key1,
@@ -236,21 +238,210 @@ impl Display for Key
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifiers
+{
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Modifiers
+{
+    pub fn none(&self) -> bool
+    {
+        !self.ctrl && !self.shift && !self.alt
+    }
+}
+
+//modifier keys held down, left and right apart so letting go of one side keeps the other one working
+#[derive(Clone, Copy, Default)]
+pub(crate) struct HeldModifiers
+{
+    ctrl: [bool; 2],
+    shift: [bool; 2],
+    alt: [bool; 2],
+}
+
+impl HeldModifiers
+{
+    pub(crate) fn update(&mut self, key: Key, pressed: bool)
+    {
+        match key
+        {
+            Key::LControl => self.ctrl[0] = pressed,
+            Key::RControl => self.ctrl[1] = pressed,
+            Key::LShift => self.shift[0] = pressed,
+            Key::RShift => self.shift[1] = pressed,
+            Key::LAlt => self.alt[0] = pressed,
+            Key::RAlt => self.alt[1] = pressed,
+            _ => {},
+        }
+    }
+
+    //the releases go elsewhere once the window lost the input, so nothing is held any more
+    pub(crate) fn reset(&mut self)
+    {
+        *self = Self::default();
+    }
+
+    pub(crate) fn get(&self) -> Modifiers
+    {
+        Modifiers { ctrl: self.ctrl[0] || self.ctrl[1], shift: self.shift[0] || self.shift[1], alt: self.alt[0] || self.alt[1] }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyChord
+{
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyChord
+{
+    pub fn new(key: Key) -> Self
+    {
+        Self { modifiers: Modifiers::default(), key }
+    }
+
+    pub fn ctrl(mut self) -> Self
+    {
+        self.modifiers.ctrl = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self
+    {
+        self.modifiers.shift = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Self
+    {
+        self.modifiers.alt = true;
+        self
+    }
+
+    //the modifiers have to match exactly, so Ctrl+S does not fire on Ctrl+Shift+S
+    pub fn matches(&self, key: Key, modifiers: Modifiers) -> bool
+    {
+        self.key == key && self.modifiers == modifiers
+    }
+}
+
+impl Display for KeyChord
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        if self.modifiers.ctrl { write!(f, "Ctrl+")?; }
+        if self.modifiers.shift { write!(f, "Shift+")?; }
+        if self.modifiers.alt { write!(f, "Alt+")?; }
+        write!(f, "{}", self.key)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton
+{
+    South,
+    East,
+    West,
+    North,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    LeftShoulder,
+    RightShoulder,
+    View,
+    Menu,
+}
+
+//stick axes point right/down for positive values, like the ui coordinates
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadAxis
+{
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TouchPhase
+{
+    Start,
+    Move,
+    End,
+    Cancel,
+}
+
 #[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HardwareEvent
 {
-    RawMouseDelta(Vec2),
-    PointerMoved { pos: Vec2, delta: Vec2 },
-    PointerClicked { pos: Vec2, button: MouseButton, pressed: bool },
+    RawMouseDelta(#[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] Vec2),
+    PointerMoved
+    {
+        #[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] pos: Vec2,
+        #[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] delta: Vec2,
+    },
+    PointerClicked
+    {
+        #[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] pos: Vec2,
+        button: MouseButton,
+        pressed: bool,
+    },
     PointerGone,
     CloseWindow,
-    Scroll { pos: Vec2, delta: Vec2 },
+    Scroll
+    {
+        #[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] pos: Vec2,
+        #[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] delta: Vec2,
+    },
     Key { key: Key, pressed: bool },
     Char(char),
+    GamepadButton { button: GamepadButton, pressed: bool },
+    GamepadAxis { axis: GamepadAxis, value: f32 },
+    //ongoing composition of an input method, cursor is a byte offset into text
+    ImePreedit { text: String, cursor: Option<usize> },
+    ImeCommit(String),
+    Touch
+    {
+        id: u64,
+        phase: TouchPhase,
+        #[cfg_attr(feature = "serde", serde(with = "crate::replay::Vec2Def"))] pos: Vec2,
+    },
 }
 
 impl HardwareEvent
 {
+    //keys and text are meant for the focused widget
+    pub(crate) fn targets_focus(&self) -> bool
+    {
+        matches!(self, Self::Key { .. } | Self::Char(_) | Self::ImePreedit { .. } | Self::ImeCommit(_))
+    }
+
+    pub fn pos(&self) -> Option<Vec2>
+    {
+        match self
+        {
+            Self::PointerMoved { pos, .. } => Some(*pos),
+            Self::PointerClicked { pos, .. } => Some(*pos),
+            Self::Scroll { pos, .. } => Some(*pos),
+            Self::Touch { pos, .. } => Some(*pos),
+            _ => None,
+        }
+    }
+
     fn scale(&mut self, scale: f32) -> &mut Self
     {
         match self
@@ -261,6 +452,8 @@ impl HardwareEvent
                 *delta *= scale;
             },
             Self::PointerClicked { pos, .. } => *pos *= scale,
+            Self::Scroll { pos, .. } => *pos *= scale,
+            Self::Touch { pos, .. } => *pos *= scale,
             _ => {},
         }
         self
@@ -272,6 +465,8 @@ impl HardwareEvent
         {
             Self::PointerMoved { pos, .. } => *pos += offset,
             Self::PointerClicked { pos, .. } => *pos += offset,
+            Self::Scroll { pos, .. } => *pos += offset,
+            Self::Touch { pos, .. } => *pos += offset,
             _ => {},
         }
         self
@@ -300,12 +495,16 @@ pub enum LogicEvent<T>
 {
     Clicked(T, MouseButton),
     Pressed(T, Key, bool),
+    Shortcut(T),
+    //a menu entry was picked, by pointer, keyboard or gamepad alike
+    Chosen(T),
 }
 
 pub enum Event<T>
 {
     Hardware(HardwareEventPod),
     Logic(LogicEvent<T>),
+    Gesture(Gesture),
 }
 
 pub enum SyntheticEvent
@@ -315,26 +514,66 @@ pub enum SyntheticEvent
     HotEnter,
     HotLeave,
     Clicked(Option<MouseButton>),
+    FocusGained,
+    FocusLost,
 }
 
 pub enum WidgetEvent<'a>
 {
     NewData,
     Hardware(&'a mut HardwareEventPod),
+    Layer(&'a mut HardwareEventPod),
+    //keys and text go to the focused widget first, the focused Response passes them on as Hardware events
+    Focus(&'a mut HardwareEventPod),
     Synthetic(SyntheticEvent),
+    FocusChanged,
+    Timer(TimerId),
+    Gesture(Gesture),
 }
 
 impl<'a> WidgetEvent<'a>
 {
     pub(crate) fn scale(&mut self, scale: f32) -> &mut Self
     {
-        if let Self::Hardware(event) = self { event.event.scale(scale); }
+        if let Self::Hardware(event) | Self::Layer(event) = self { event.event.scale(scale); }
         self
     }
 
+    pub(crate) fn into_hardware(self) -> Self
+    {
+        match self
+        {
+            Self::Layer(event) | Self::Focus(event) => Self::Hardware(event),
+            event => event,
+        }
+    }
+
+    pub(crate) fn into_focus(self) -> Self
+    {
+        match self
+        {
+            Self::Hardware(event) | Self::Layer(event) => Self::Focus(event),
+            event => event,
+        }
+    }
+
+    pub(crate) fn into_layer(self) -> Self
+    {
+        match self
+        {
+            Self::Hardware(event) => Self::Layer(event),
+            event => event,
+        }
+    }
+
     pub(crate) fn offset(&mut self, offset: Vec2) -> &mut Self
     {
-        if let Self::Hardware(event) = self { event.event.offset(offset); }
+        match self
+        {
+            Self::Hardware(event) => { event.event.offset(offset); },
+            Self::Gesture(gesture) => { gesture.offset(offset); },
+            _ => {},
+        }
         self
     }
 }
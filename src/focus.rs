@@ -0,0 +1,160 @@
+use crate::{state::WidgetId, math::Rect, event::{HardwareEvent, HardwareEventPod, Key, GamepadButton, GamepadAxis}};
+
+const STICK_PRESS: f32 = 0.5;
+const STICK_RELEASE: f32 = 0.3;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Direction
+{
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+pub(crate) struct Focus
+{
+    current: Option<WidgetId>,
+    changed: bool,
+    //tab order and absolute rects, collected while painting
+    order: Vec<(WidgetId, Rect)>,
+    stick_armed: [bool; 2],
+}
+
+impl Focus
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { current: None, changed: false, order: Vec::new(), stick_armed: [true; 2] }
+    }
+
+    #[inline] pub(crate) fn current(&self) -> Option<WidgetId> { self.current }
+
+    pub(crate) fn set(&mut self, id: Option<WidgetId>)
+    {
+        if self.current != id
+        {
+            self.current = id;
+            self.changed = true;
+        }
+    }
+
+    pub(crate) fn take_changed(&mut self) -> bool
+    {
+        std::mem::replace(&mut self.changed, false)
+    }
+
+    pub(crate) fn clear_order(&mut self)
+    {
+        self.order.clear();
+    }
+
+    pub(crate) fn register(&mut self, id: WidgetId, rect: Rect)
+    {
+        self.order.push((id, rect));
+    }
+
+    //whether the focused widget registered after the mark, i.e. lies in the subtree painted since
+    #[inline] pub(crate) fn mark(&self) -> usize { self.order.len() }
+    #[inline] pub(crate) fn current_since(&self, mark: usize) -> bool { self.current.is_some_and(|id| self.order[mark..].iter().any(|(other, _)| *other == id)) }
+
+    fn current_index(&self) -> Option<usize>
+    {
+        self.current.and_then(|id| self.order.iter().position(|(other, _)| *other == id))
+    }
+
+    pub(crate) fn advance(&mut self, backwards: bool)
+    {
+        let len = self.order.len();
+        if len == 0 { return; }
+        let next = match self.current_index()
+        {
+            Some(i) => if backwards { (i + len - 1) % len } else { (i + 1) % len },
+            None => if backwards { len - 1 } else { 0 },
+        };
+        self.set(Some(self.order[next].0));
+    }
+
+    //nearest focusable in the given half plane, sideways distance weighs more than distance along the direction
+    pub(crate) fn navigate(&mut self, direction: Direction)
+    {
+        let Some(i) = self.current_index() else
+        {
+            self.advance(false);
+            return;
+        };
+        let center = |rect: &Rect| (rect.min + rect.max) / 2.0;
+        let from = center(&self.order[i].1);
+        let mut best: Option<(f32, WidgetId)> = None;
+        for (j, (id, rect)) in self.order.iter().enumerate()
+        {
+            if j == i { continue; }
+            let delta = center(rect) - from;
+            let (primary, secondary) = match direction
+            {
+                Direction::Left => (-delta.0, delta.1),
+                Direction::Right => (delta.0, delta.1),
+                Direction::Up => (-delta.1, delta.0),
+                Direction::Down => (delta.1, delta.0),
+            };
+            if primary <= 0.0 { continue; }
+            let score = primary + 2.0 * secondary.abs();
+            if best.is_none_or(|(best_score, _)| score < best_score) { best = Some((score, *id)); }
+        }
+        if let Some((_, id)) = best { self.set(Some(id)); }
+    }
+
+    //a stick acts like a dpad that has to come back close to the center before it fires again
+    pub(crate) fn stick(&mut self, vertical: bool, value: f32) -> Option<Direction>
+    {
+        let armed = &mut self.stick_armed[vertical as usize];
+        if value.abs() < STICK_RELEASE { *armed = true; }
+        if value.abs() < STICK_PRESS || !*armed { return None; }
+        *armed = false;
+        Some(match (vertical, value > 0.0)
+        {
+            (false, false) => Direction::Left,
+            (false, true) => Direction::Right,
+            (true, false) => Direction::Up,
+            (true, true) => Direction::Down,
+        })
+    }
+
+    //tab order, arrow keys, dpad and left stick move the focus, back drops it
+    //the input only counts as used if the focus changed, so games still get it when there is nothing to focus
+    pub(crate) fn navigation(&mut self, event: &mut HardwareEventPod, backwards: bool)
+    {
+        if event.used { return; }
+        let before = self.current;
+        let direction = match event.event
+        {
+            HardwareEvent::Key { key: Key::Tab, pressed: true } =>
+            {
+                self.advance(backwards);
+                event.used = self.current != before;
+                return;
+            },
+            HardwareEvent::GamepadButton { button: GamepadButton::East, pressed: true } =>
+            {
+                if self.current.is_some()
+                {
+                    self.set(None);
+                    event.used = true;
+                }
+                return;
+            },
+            HardwareEvent::Key { key: Key::Left, pressed: true } | HardwareEvent::GamepadButton { button: GamepadButton::DPadLeft, pressed: true } => Some(Direction::Left),
+            HardwareEvent::Key { key: Key::Right, pressed: true } | HardwareEvent::GamepadButton { button: GamepadButton::DPadRight, pressed: true } => Some(Direction::Right),
+            HardwareEvent::Key { key: Key::Up, pressed: true } | HardwareEvent::GamepadButton { button: GamepadButton::DPadUp, pressed: true } => Some(Direction::Up),
+            HardwareEvent::Key { key: Key::Down, pressed: true } | HardwareEvent::GamepadButton { button: GamepadButton::DPadDown, pressed: true } => Some(Direction::Down),
+            HardwareEvent::GamepadAxis { axis: GamepadAxis::LeftStickX, value } => self.stick(false, value),
+            HardwareEvent::GamepadAxis { axis: GamepadAxis::LeftStickY, value } => self.stick(true, value),
+            _ => None,
+        };
+        if let Some(direction) = direction
+        {
+            self.navigate(direction);
+            event.used = self.current != before;
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{math::Vec2, event::{HardwareEvent, MouseButton, TouchPhase}};
+
+const TAP_TIME: f32 = 0.3;
+const TAP_DISTANCE: f32 = 0.5;
+const LONG_PRESS_TIME: f32 = 0.6;
+const SWIPE_TIME: f32 = 0.5;
+const SWIPE_DISTANCE: f32 = 3.0;
+
+//positions in ui units
+#[derive(Clone, Copy, PartialEq)]
+pub enum Gesture
+{
+    Tap { pos: Vec2 },
+    LongPress { pos: Vec2 },
+    Swipe { start: Vec2, delta: Vec2 },
+    //scale is relative to the last pinch event
+    Pinch { center: Vec2, scale: f32 },
+}
+
+impl Gesture
+{
+    pub(crate) fn offset(&mut self, offset: Vec2) -> &mut Self
+    {
+        match self
+        {
+            Self::Tap { pos } | Self::LongPress { pos } => *pos += offset,
+            Self::Swipe { start, .. } => *start += offset,
+            Self::Pinch { center, .. } => *center += offset,
+        }
+        self
+    }
+}
+
+fn length(v: Vec2) -> f32
+{
+    (v.0 * v.0 + v.1 * v.1).sqrt()
+}
+
+struct Finger
+{
+    id: u64,
+    start: Vec2,
+    start_time: f32,
+    pos: Vec2,
+    long_pressed: bool,
+}
+
+pub(crate) struct Touches
+{
+    fingers: Vec<Finger>,
+    //the finger that drives the pointer
+    primary: Option<u64>,
+    //more than one finger was down since the first one touched
+    multi: bool,
+}
+
+impl Touches
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { fingers: Vec::new(), primary: None, multi: false }
+    }
+
+    //returns the pointer events standing in for the primary finger (in pixels like the touch) and a recognized gesture
+    pub(crate) fn touch(&mut self, id: u64, phase: TouchPhase, pixel_pos: Vec2, scale: f32, time: f32) -> (Vec<HardwareEvent>, Option<Gesture>)
+    {
+        let pos = pixel_pos / scale;
+        let mut pointer = Vec::new();
+        let mut gesture = None;
+        let index = self.fingers.iter().position(|finger| finger.id == id);
+        match (phase, index)
+        {
+            (TouchPhase::Start, None) =>
+            {
+                if self.fingers.is_empty()
+                {
+                    self.primary = Some(id);
+                    self.multi = false;
+                    pointer.push(HardwareEvent::PointerMoved { pos: pixel_pos, delta: Vec2::zero() });
+                    pointer.push(HardwareEvent::PointerClicked { pos: pixel_pos, button: MouseButton::Primary, pressed: true });
+                } else
+                {
+                    //a second finger turns it into a multi-touch gesture, so the pointer lets go
+                    if self.primary.take().is_some() { pointer.push(HardwareEvent::PointerGone); }
+                    self.multi = true;
+                }
+                self.fingers.push(Finger { id, start: pos, start_time: time, pos, long_pressed: false });
+            },
+            (TouchPhase::Move, Some(i)) =>
+            {
+                let old = self.fingers[i].pos;
+                if self.primary == Some(id) { pointer.push(HardwareEvent::PointerMoved { pos: pixel_pos, delta: (pos - old) * scale }); }
+                if self.fingers.len() == 2
+                {
+                    let other = self.fingers[1 - i].pos;
+                    let (before, after) = (length(old - other), length(pos - other));
+                    if before > 0.0 { gesture = Some(Gesture::Pinch { center: (pos + other) / 2.0, scale: after / before }); }
+                }
+                self.fingers[i].pos = pos;
+            },
+            (TouchPhase::End | TouchPhase::Cancel, Some(i)) =>
+            {
+                let finger = self.fingers.remove(i);
+                if self.primary == Some(id)
+                {
+                    self.primary = None;
+                    if phase == TouchPhase::End { pointer.push(HardwareEvent::PointerClicked { pos: pixel_pos, button: MouseButton::Primary, pressed: false }); }
+                    pointer.push(HardwareEvent::PointerGone);
+                }
+                if phase == TouchPhase::End && !self.multi && !finger.long_pressed
+                {
+                    let delta = pos - finger.start;
+                    let duration = time - finger.start_time;
+                    if length(delta) < TAP_DISTANCE && duration <= TAP_TIME { gesture = Some(Gesture::Tap { pos }); }
+                    else if length(delta) >= SWIPE_DISTANCE && duration <= SWIPE_TIME { gesture = Some(Gesture::Swipe { start: finger.start, delta }); }
+                }
+            },
+            _ => {},
+        }
+        (pointer, gesture)
+    }
+
+    //long presses fire while the finger is still down
+    pub(crate) fn update(&mut self, time: f32) -> Option<Gesture>
+    {
+        if self.multi { return None; }
+        let [finger] = &mut self.fingers[..] else { return None; };
+        if finger.long_pressed || length(finger.pos - finger.start) >= TAP_DISTANCE || time - finger.start_time < LONG_PRESS_TIME { return None; }
+        finger.long_pressed = true;
+        Some(Gesture::LongPress { pos: finger.pos })
+    }
+
+    pub(crate) fn next_deadline(&self) -> Option<f32>
+    {
+        match &self.fingers[..]
+        {
+            [finger] if !self.multi && !finger.long_pressed && length(finger.pos - finger.start) < TAP_DISTANCE => Some(finger.start_time + LONG_PRESS_TIME),
+            _ => None,
+        }
+    }
+}
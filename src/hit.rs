@@ -0,0 +1,48 @@
+use crate::{math::{Vec2, Rect}, state::WidgetId};
+
+#[derive(Clone)]
+pub struct Hit
+{
+    pub name: &'static str,
+    pub id: Option<WidgetId>,
+    //absolute, in ui units and already cut by the clip rects around the widget
+    pub rect: Rect,
+    //containers are transparent for the pointer, backgrounds and interactive widgets are not
+    pub solid: bool,
+    layer: usize,
+}
+
+pub(crate) struct Registry
+{
+    //in paint order
+    hits: Vec<Hit>,
+}
+
+impl Registry
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { hits: Vec::new() }
+    }
+
+    pub(crate) fn clear(&mut self)
+    {
+        self.hits.clear();
+    }
+
+    pub(crate) fn record(&mut self, name: &'static str, id: Option<WidgetId>, rect: Rect, solid: bool, layer: usize)
+    {
+        if rect.min.0 >= rect.max.0 || rect.min.1 >= rect.max.1 { return; }
+        self.hits.push(Hit { name, id, rect, solid, layer });
+    }
+
+    #[inline] pub(crate) fn hits(&self) -> &[Hit] { &self.hits }
+
+    //topmost first: higher layers before lower ones, later painted before earlier painted
+    pub(crate) fn hit_test(&self, pos: Vec2) -> Vec<&Hit>
+    {
+        let mut stack: Vec<&Hit> = self.hits.iter().rev().filter(|hit| hit.rect.contains_linf(pos)).collect();
+        stack.sort_by_key(|hit| std::cmp::Reverse(hit.layer));
+        stack
+    }
+}
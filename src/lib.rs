@@ -4,17 +4,30 @@ pub mod event;
 pub mod paint;
 pub mod widget;
 pub mod lens;
+pub mod testing;
+pub mod state;
+pub mod anim;
+pub mod hit;
+pub mod access;
+pub mod timer;
+pub mod cursor;
+pub mod gesture;
+#[cfg(feature = "serde")] pub mod replay;
+mod focus;
+mod debug;
 
 use paint::style;
 use widget::*;
 
 const DEFAULT_SCALE: f32 = 20.0;
+const ERR: f32 = 1e-3;
 
 pub struct Request
 {
     widget: bool,
     layout: bool,
     paint: bool,
+    animate: bool,
 }
 
 impl Request
@@ -37,11 +50,19 @@ impl Request
         self.paint = true;
     }
 
+    //repaint now and again next frame
+    pub fn animate(&mut self)
+    {
+        self.paint = true;
+        self.animate = true;
+    }
+
     fn reset(&mut self)
     {
         self.widget = false;
         self.layout = false;
         self.paint = false;
+        self.animate = false;
     }
 }
 
@@ -50,9 +71,18 @@ pub struct EventCtx<'a, E>
     pub request: &'a mut Request,
     pub event: event::WidgetEvent<'a>,
     events: &'a mut Vec<event::Event<E>>,
+    focus: &'a mut focus::Focus,
+    timers: &'a mut timer::Timers,
+    pointer: &'a mut cursor::Pointer,
+    modifiers: event::Modifiers,
+    time: f32,
+    dt: f32,
 }
 
-pub struct WidgetComputeCtx;
+pub struct WidgetComputeCtx<'a>
+{
+    store: &'a mut state::StateStore,
+}
 
 pub struct LayoutInquireCtx<'a>
 {
@@ -61,8 +91,10 @@ pub struct LayoutInquireCtx<'a>
 
 pub struct LayoutComputeCtx<'a>
 {
-    fits: &'a mut bool,
+    misfits: &'a mut Vec<Misfit>,
+    path: &'a mut Vec<(&'static str, Option<usize>)>,
     painter: &'a mut paint::Painter,
+    screen: math::Vec2,
 }
 
 pub struct PaintCtx<'a>
@@ -70,22 +102,81 @@ pub struct PaintCtx<'a>
     painter: &'a mut paint::Painter,
     style: &'a mut style::StyleSet,
     state: interact::WidgetState,
+    screen: math::Vec2,
+    focus: &'a mut focus::Focus,
+    focused: bool,
+    state_from: interact::WidgetState,
+    state_blend: f32,
+    time: f32,
+    hits: &'a mut hit::Registry,
+    access: &'a mut access::AccessTree,
+    debug: &'a mut debug::Debug,
+    pointer: &'a mut cursor::Pointer,
+    ime_caret: &'a mut Option<math::Rect>,
+    animate: &'a mut bool,
+    //painting a preview, e.g. of a drag, that must not register for focus, hits, accessibility or the ime
+    ghost: bool,
 }
 
 impl<'a, E> EventCtx<'a, E>
 {
     #[inline] pub fn emit(&mut self, event: event::LogicEvent<E>) { self.events.push(event::Event::Logic(event)); }
+    #[inline] pub fn time(&self) -> f32 { self.time }
+    #[inline] pub fn dt(&self) -> f32 { self.dt }
+    #[inline] pub fn modifiers(&self) -> event::Modifiers { self.modifiers }
+    #[inline] pub fn focused(&self) -> Option<state::WidgetId> { self.focus.current() }
+    #[inline] pub fn request_focus(&mut self, id: state::WidgetId) { self.focus.set(Some(id)); }
+    #[inline] pub fn clear_focus(&mut self) { self.focus.set(None); }
+    //comes back as WidgetEvent::Timer in the first frame at least delay seconds from now
+    #[inline] pub fn schedule(&mut self, delay: f32) -> timer::TimerId { self.timers.schedule(self.time + delay) }
+    #[inline] pub fn cancel(&mut self, id: timer::TimerId) { self.timers.cancel(id); }
+    #[inline] pub fn set_cursor(&mut self, cursor: cursor::CursorIcon) { self.pointer.cursor = cursor; }
+    #[inline] pub fn grab_pointer(&mut self) { self.pointer.grab = true; }
+    #[inline] pub fn release_pointer(&mut self) { self.pointer.grab = false; }
+    #[inline] pub fn pointer_grabbed(&self) -> bool { self.pointer.grab }
+}
+
+impl<'a> WidgetComputeCtx<'a>
+{
+    #[inline] pub fn state<S: Copy + Default + 'static>(&mut self, id: state::WidgetId) -> state::Persistent<S> { self.store.with_id(id) }
+    #[inline] pub fn next_state<S: Copy + Default + 'static>(&mut self) -> state::Persistent<S> { self.store.next() }
 }
 
 impl<'a> LayoutInquireCtx<'a>
 {
     #[inline] pub fn text_width(&mut self, text: &str, size: f32) -> f32 { self.painter.text_width(text, size) }
     #[inline] pub fn text_height(&mut self, text: &str, layout: text::Layout) -> u32 { self.painter.text_height(text, layout) }
+    //one texel per pixel at scale 1
+    #[inline] pub fn texture_size(&self, id: paint::TextureId) -> Option<math::Vec2> { self.painter.texture_size(id).map(|(width, height)| math::Vec2(width as f32, height as f32) / DEFAULT_SCALE) }
 }
 
 impl<'a> LayoutComputeCtx<'a>
 {
-    #[inline] pub fn does_not_fit(&mut self) { *self.fits = false; }
+    #[inline] pub fn push_path(&mut self, name: &'static str, index: Option<usize>) { self.path.push((name, index)); }
+    #[inline] pub fn pop_path(&mut self) { self.path.pop(); }
+    //returns the index of the misfit, hand it to PaintCtx::misfit so the debug overlay can show where it is
+    pub fn does_not_fit(&mut self, name: &'static str, requested: math::Vec2, available: math::Vec2) -> Option<usize>
+    {
+        let axis = match (requested.0 > available.0 + ERR, requested.1 > available.1 + ERR)
+        {
+            (true, true) => Axis::Both,
+            (true, false) => Axis::Horizontal,
+            (false, true) => Axis::Vertical,
+            //e.g. padding that takes up exactly the allotted size, nothing is cut off
+            (false, false) => return None,
+        };
+        let mut path = String::new();
+        for (i, (segment, index)) in self.path.iter().enumerate()
+        {
+            if i > 0 { path.push('/'); }
+            path.push_str(segment);
+            if let Some(index) = index { path.push_str(&format!("[{}]", index)); }
+        }
+        self.misfits.push(Misfit { path, name, requested, available, axis });
+        Some(self.misfits.len() - 1)
+    }
+    #[inline] pub fn screen_size(&self) -> math::Vec2 { self.screen }
+    #[inline] pub fn inquire(&mut self) -> LayoutInquireCtx<'_> { LayoutInquireCtx { painter: &mut *self.painter } }
     #[inline] pub fn text_width(&mut self, text: &str, size: f32) -> f32 { self.painter.text_width(text, size) }
     #[inline] pub fn text_height(&mut self, text: &str, layout: text::Layout) -> u32 { self.painter.text_height(text, layout) }
 }
@@ -93,9 +184,50 @@ impl<'a> LayoutComputeCtx<'a>
 impl<'a> PaintCtx<'a>
 {
     #[inline] pub fn add_offset(&mut self, offset: math::Vec2) { self.painter.add_offset(offset); }
+    #[inline] pub fn origin(&self) -> math::Vec2 { self.painter.origin() }
+    #[inline] pub fn screen_size(&self) -> math::Vec2 { self.screen }
+    #[inline] pub fn push_layer(&mut self, origin: math::Vec2) { self.painter.push_layer(origin); }
+    #[inline] pub fn pop_layer(&mut self) { self.painter.pop_layer(); }
+    #[inline] pub fn push_clip(&mut self, rect: math::Rect) { self.painter.push_clip(rect); }
+    #[inline] pub fn pop_clip(&mut self) { self.painter.pop_clip(); }
+    #[inline] pub fn focused(&self) -> Option<state::WidgetId> { self.focus.current() }
+    #[inline] pub fn focusable(&mut self, id: state::WidgetId, size: math::Vec2) { if !self.ghost { self.focus.register(id, math::Rect::new_size(self.painter.origin(), size)); } }
+    #[inline] pub fn is_focused(&self) -> bool { self.focused }
+    #[inline] pub fn time(&self) -> f32 { self.time }
+    //paint again next frame, for animations driven by time()
+    #[inline] pub fn animate(&mut self) { *self.animate = true; }
+    #[inline] pub fn color(&self, set: &style::ColorSet) -> paint::Color { set.blend(self.state_from, self.state, self.state_blend) }
+    pub fn record(&mut self, name: &'static str, id: Option<state::WidgetId>, size: math::Vec2, solid: bool)
+    {
+        if self.ghost { return; }
+        let rect = math::Rect::new_size(self.painter.origin(), size);
+        let rect = match self.painter.clip_rect() { Some(clip) => paint::intersect(clip, rect), None => rect };
+        self.hits.record(name, id, rect, solid, self.painter.layer());
+    }
+    #[inline] pub fn set_cursor(&mut self, cursor: cursor::CursorIcon) { self.pointer.cursor = cursor; }
+    #[inline]
+    pub fn set_ime_caret(&mut self, rect: math::Rect)
+    {
+        if self.ghost { return; }
+        let origin = self.painter.origin();
+        *self.ime_caret = Some(math::Rect { min: origin + rect.min, max: origin + rect.max });
+    }
+    //the widget behind the misfit is at the current origin
+    #[inline] pub fn misfit(&mut self, index: usize) { if !self.ghost { self.debug.locate(index, self.painter.origin()); } }
+    #[inline] pub fn accessible(&self) -> bool { self.access.enabled() && !self.ghost }
+    #[inline] pub fn access_push(&mut self, role: access::Role, size: math::Vec2) { if !self.ghost { self.access.push(role, math::Rect::new_size(self.painter.origin(), size), self.focused); } }
+    #[inline] pub fn access_pop(&mut self) { if !self.ghost { self.access.pop(); } }
+    #[inline]
+    pub fn access_leaf(&mut self, role: access::Role, name: &str, value: Option<String>, checked: Option<bool>, size: math::Vec2)
+    {
+        if self.ghost { return; }
+        self.access.leaf(role, name, value, checked, math::Rect::new_size(self.painter.origin(), size), self.focused);
+    }
+    #[inline] pub fn draw_outline(&mut self, rect: math::Rect, width: f32, color: paint::Color) { self.painter.draw_outline(rect, width, color); }
     #[inline] pub fn draw_rect(&mut self, rect: math::Rect, color: paint::Color) { self.painter.draw_rect(rect, color); }
     #[inline] pub fn draw_rhombus(&mut self, rect: math::Rect, color: paint::Color) { self.painter.draw_rhombus(rect, color); }
     #[inline] pub fn draw_text(&mut self, rect: math::Rect, text: &str, size: f32, align: text::Align, auto_wrap: bool, color: paint::Color) { self.painter.draw_text(rect, text, size, align, auto_wrap, color); }
+    #[inline] pub fn draw_image(&mut self, rect: math::Rect, texture: paint::TextureId, uv_rect: math::Rect, tint: paint::Color) { self.painter.draw_image(rect, texture, uv_rect, tint); }
 }
 
 pub trait Widget<T, E>
@@ -107,17 +239,46 @@ pub trait Widget<T, E>
 }
 
 #[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UiConfig
 {
+    #[cfg_attr(feature = "serde", serde(with = "replay::Vec2Def"))]
     pub size: math::Vec2,
     pub scale: f32,
     pub display_scale_factor: f32,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum Axis
+{
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+//path is made of the containers around the widget, e.g. "Column[2]/Padding/Row[0]"
+#[derive(Clone)]
+pub struct Misfit
+{
+    pub path: String,
+    pub name: &'static str,
+    pub requested: math::Vec2,
+    pub available: math::Vec2,
+    pub axis: Axis,
+}
+
 pub struct Frame<'a, E>
 {
     pub px_per_ui_unit: f32,
     pub fits: bool,
+    pub misfits: &'a [Misfit],
+    pub animate: bool,
+    //seconds until the next scheduled timer
+    pub next_deadline: Option<f32>,
+    pub cursor: cursor::CursorIcon,
+    pub pointer_grab: bool,
+    //text caret of the focused text field in pixels, for placing the ime candidate window
+    pub ime_caret: Option<math::Rect>,
     pub events: &'a mut [event::Event<E>],
     pub paint: paint::Frame<'a>,
     pub request: &'a mut Request,
@@ -137,6 +298,23 @@ pub struct Ui<'a, T: 'a, E>
     events: Vec<event::Event<E>>,
     painter: paint::Painter,
     style: style::StyleSet,
+    store: state::StateStore,
+    focus: focus::Focus,
+    timers: timer::Timers,
+    pointer: cursor::Pointer,
+    touches: gesture::Touches,
+    ime_caret: Option<math::Rect>,
+    hits: hit::Registry,
+    access: access::AccessTree,
+    debug: debug::Debug,
+    misfits: Vec<Misfit>,
+    #[cfg(feature = "serde")]
+    recording: Option<replay::Recording>,
+    modifiers: event::HeldModifiers,
+    //global shortcuts, heard when no widget used the key
+    shortcuts: Vec<(event::KeyChord, Box<dyn Fn() -> E + 'a>)>,
+    time: f32,
+    animate: bool,
 }
 
 impl UiInit
@@ -148,6 +326,21 @@ impl UiInit
             painter: paint::Painter::new(font),
         }
     }
+
+    //clip rects, images and glyph quads on the cpu instead of relying on the scissor rects of the draw commands
+    //only rhombi reaching over a clip keep their scissor, cutting them would not leave a quad
+    pub fn cpu_clip(mut self) -> Self
+    {
+        self.painter.set_cpu_clip(true);
+        self
+    }
+
+    //one batch with u32 indices instead of u16 batches that each start at their own base vertex
+    pub fn wide_indices(mut self) -> Self
+    {
+        self.painter.set_wide_indices(true);
+        self
+    }
 }
 
 impl<'a, T: 'a, E> Ui<'a, T, E>
@@ -157,11 +350,19 @@ impl<'a, T: 'a, E> Ui<'a, T, E>
         let constructor = Box::new(constructor);
         let root = None;
         let config = None;
-        let request = Request { widget: true, layout: true, paint: true };
+        let request = Request { widget: true, layout: true, paint: true, animate: false };
         let events = Vec::new();
         let painter = init.painter;
         let style = style::StyleSet::default();
-        Self { constructor, root, config, request, events, painter, style }
+        let store = state::StateStore::new();
+        let focus = focus::Focus::new();
+        let timers = timer::Timers::new();
+        let pointer = cursor::Pointer::new();
+        let touches = gesture::Touches::new();
+        let hits = hit::Registry::new();
+        let access = access::AccessTree::new();
+        let debug = debug::Debug::new();
+        Self { constructor, root, config, request, events, painter, style, store, focus, timers, pointer, touches, ime_caret: None, hits, access, debug, misfits: Vec::new(), #[cfg(feature = "serde")] recording: None, modifiers: event::HeldModifiers::default(), shortcuts: Vec::new(), time: 0.0, animate: false }
     }
 
     pub fn request(&mut self) -> &mut Request
@@ -174,8 +375,100 @@ impl<'a, T: 'a, E> Ui<'a, T, E>
         &mut self.style
     }
 
-    pub fn frame<'b>(&mut self, config: UiConfig, data: &mut T, events: impl Iterator<Item = &'b event::HardwareEvent>) -> Frame<'_, E>
+    pub fn focus(&self) -> Option<state::WidgetId>
+    {
+        self.focus.current()
+    }
+
+    pub fn set_focus(&mut self, id: Option<state::WidgetId>)
+    {
+        self.focus.set(id);
+    }
+
+    fn px_per_ui_unit(&self) -> f32
+    {
+        self.config.as_ref().map_or(DEFAULT_SCALE, |config| config.scale * config.display_scale_factor * DEFAULT_SCALE)
+    }
+
+    //widgets under the given position (in pixels like the hardware events) from the last painted frame, topmost first
+    pub fn hit_test(&self, pos: math::Vec2) -> Vec<&hit::Hit>
+    {
+        self.hits.hit_test(pos / self.px_per_ui_unit())
+    }
+
+    pub fn wants_pointer(&self, pos: math::Vec2) -> bool
+    {
+        self.hit_test(pos).iter().any(|hit| hit.solid)
+    }
+
+    //the renderer owns the texture data, the ui only needs to know its size in pixels
+    pub fn add_texture(&mut self, width: u32, height: u32) -> paint::TextureId
     {
+        self.request.layout();
+        self.painter.add_texture(width, height)
+    }
+
+    pub fn remove_texture(&mut self, id: paint::TextureId)
+    {
+        self.request.layout();
+        self.painter.remove_texture(id);
+    }
+
+    //the tree is collected while painting, so it is up to date after the next frame
+    pub fn set_accessibility(&mut self, enabled: bool)
+    {
+        if enabled != self.access.enabled() { self.request.paint(); }
+        self.access.set_enabled(enabled);
+    }
+
+    pub fn access_tree(&self) -> &[access::Node]
+    {
+        self.access.nodes()
+    }
+
+    //records the config, dt and hardware events of every following frame, but not the calls on the ui between frames
+    #[cfg(feature = "serde")]
+    pub fn start_recording(&mut self)
+    {
+        self.recording = Some(replay::Recording::new());
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn stop_recording(&mut self) -> Option<replay::Recording>
+    {
+        self.recording.take()
+    }
+
+    //outlines every recorded widget and marks the ones that did not fit
+    pub fn set_debug(&mut self, enabled: bool)
+    {
+        if enabled != self.debug.enabled() { self.request.paint(); }
+        self.debug.set_enabled(enabled);
+    }
+
+    //emits LogicEvent::Shortcut with the tag when the chord is pressed and no focused scope handled it
+    pub fn shortcut(&mut self, chord: event::KeyChord, tag: E) where E: Clone + 'a
+    {
+        self.shortcuts.retain(|(other, _)| *other != chord);
+        self.shortcuts.push((chord, Box::new(move || tag.clone())));
+    }
+
+    pub fn clear_shortcuts(&mut self)
+    {
+        self.shortcuts.clear();
+    }
+
+    pub fn frame<'b>(&mut self, config: UiConfig, data: &mut T, dt: f32, events: impl Iterator<Item = &'b event::HardwareEvent>) -> Frame<'_, E>
+    {
+        //time
+        self.time += dt;
+        let time = self.time;
+        if self.animate { self.request.paint(); }
+
+        //recording
+        #[cfg(feature = "serde")]
+        let mut recorded = self.recording.as_ref().map(|_| replay::RecordedFrame { config: config.clone(), dt, events: Vec::new() });
+
         //config
         let config = Some(config);
         if self.config != config { self.request.layout(); }
@@ -187,72 +480,171 @@ impl<'a, T: 'a, E> Ui<'a, T, E>
         //widget computer
         let root_compute = |ui: &mut Self, data: &mut T|
         {
-            let mut ctx = WidgetComputeCtx;
+            ui.store.begin();
+            let mut ctx = WidgetComputeCtx { store: &mut ui.store };
             ui.root = Some((ui.constructor)(&mut ctx, data));
+            ui.store.end();
         };
 
-        //"new data" event sender
-        let new_data_send = |request: &mut Request, events: &mut Vec<event::Event<E>>, widget: &mut dyn Widget<T, E>, data: &mut T|
+        //"new data"/"focus changed"/timer event sender
+        let broadcast = |request: &mut Request, events: &mut Vec<event::Event<E>>, focus: &mut focus::Focus, timers: &mut timer::Timers, pointer: &mut cursor::Pointer, modifiers: event::Modifiers, widget: &mut dyn Widget<T, E>, data: &mut T, event: event::WidgetEvent<'static>|
         {
-            let mut ctx = EventCtx { request, event: event::WidgetEvent::NewData, events };
+            let mut ctx = EventCtx { request, event, events, focus, timers, pointer, modifiers, time, dt };
             widget.event(&mut ctx, data);
         };
 
+        //hardware event sender (positions in pixels)
+        let dispatch = |request: &mut Request, events: &mut Vec<event::Event<E>>, focus: &mut focus::Focus, timers: &mut timer::Timers, pointer: &mut cursor::Pointer, modifiers: event::Modifiers, widget: &mut dyn Widget<T, E>, data: &mut T, hardware_event: &mut event::HardwareEventPod|
+        {
+            if hardware_event.event.pos().is_some() || hardware_event.event == event::HardwareEvent::PointerGone { pointer.cursor = cursor::CursorIcon::Default; }
+            let mut ctx = EventCtx { request, event: event::WidgetEvent::Layer(hardware_event), events, focus, timers, pointer, modifiers, time, dt };
+
+            ctx.event.scale(1.0 / scale);
+            //overlay layers get the first chance to react
+            widget.event(&mut ctx, data);
+            //then the focused widget for keys and text
+            let focused = ctx.focus.current().is_some() && matches!(&ctx.event, event::WidgetEvent::Layer(event) if !event.used && event.event.targets_focus());
+            if focused
+            {
+                ctx.event = std::mem::replace(&mut ctx.event, event::WidgetEvent::NewData).into_focus();
+                widget.event(&mut ctx, data);
+            }
+            ctx.event = std::mem::replace(&mut ctx.event, event::WidgetEvent::NewData).into_hardware();
+            widget.event(&mut ctx, data);
+            ctx.event.scale(scale);
+            //a grab does not outlive the pointer leaving the window
+            if let event::WidgetEvent::Hardware(hardware_event) = &ctx.event && hardware_event.event == event::HardwareEvent::PointerGone { ctx.pointer.grab = false; }
+            //no drop target took the payload
+            if let event::WidgetEvent::Hardware(hardware_event) = &ctx.event
+                && let event::HardwareEvent::PointerClicked { button: event::MouseButton::Primary, pressed: false, .. } | event::HardwareEvent::PointerGone = hardware_event.event
+            {
+                ctx.pointer.drag = None;
+            }
+        };
+
         //init root on first frame
         if self.root.is_none() { root_compute(self, data); }
         let root = self.root.as_mut().unwrap();
 
         self.events.clear();
         //"new data" each frame
-        new_data_send(&mut self.request, &mut self.events, root, data);
+        broadcast(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, event::WidgetEvent::NewData);
+        //timers that ran out
+        for id in self.timers.take_due(time)
+        {
+            broadcast(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, event::WidgetEvent::Timer(id));
+        }
+        //long presses
+        if let Some(gesture) = self.touches.update(time)
+        {
+            broadcast(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, event::WidgetEvent::Gesture(gesture));
+            self.events.push(event::Event::Gesture(gesture));
+        }
         //external events
         for event in events
         {
+            #[cfg(feature = "serde")]
+            if let Some(recorded) = &mut recorded { recorded.events.push(event.clone()); }
+            let (emulated, gesture) = match event
+            {
+                event::HardwareEvent::Touch { id, phase, pos } => self.touches.touch(*id, *phase, *pos, scale, time),
+                _ => (Vec::new(), None),
+            };
+            match event
+            {
+                event::HardwareEvent::Key { key, pressed } => self.modifiers.update(*key, *pressed),
+                event::HardwareEvent::PointerGone | event::HardwareEvent::CloseWindow => self.modifiers.reset(),
+                _ => {},
+            }
             let mut hardware_event = event::HardwareEventPod::new(event.clone());
-            let mut ctx = EventCtx { request: &mut self.request, event: event::WidgetEvent::Hardware(&mut hardware_event), events: &mut self.events };
-            
-            ctx.event.scale(1.0 / scale);
-            root.event(&mut ctx, data);
-            ctx.event.scale(scale);
+            dispatch(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, &mut hardware_event);
+            //touch-unaware widgets still work through the primary finger acting as the pointer
+            for emulated in emulated
+            {
+                let mut emulated = event::HardwareEventPod::new(emulated);
+                dispatch(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, &mut emulated);
+                hardware_event.used |= emulated.used;
+            }
+
+            //global shortcuts and focus navigation with whatever the widgets left over
+            if !hardware_event.used && let event::HardwareEvent::Key { key, pressed: true } = hardware_event.event
+                && let Some((_, tag)) = self.shortcuts.iter().find(|(chord, _)| chord.matches(key, self.modifiers.get()))
+            {
+                hardware_event.used = true;
+                self.events.push(event::Event::Logic(event::LogicEvent::Shortcut(tag())));
+            }
+            self.focus.navigation(&mut hardware_event, self.modifiers.get().shift);
             
             self.events.push(event::Event::Hardware(hardware_event));
+            if let Some(gesture) = gesture
+            {
+                broadcast(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, event::WidgetEvent::Gesture(gesture));
+                self.events.push(event::Event::Gesture(gesture));
+            }
+        }
+        #[cfg(feature = "serde")]
+        if let (Some(recording), Some(recorded)) = (&mut self.recording, recorded) { recording.frames.push(recorded); }
+        if self.focus.take_changed()
+        {
+            broadcast(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, event::WidgetEvent::FocusChanged);
         }
 
         //compute widgets
         let root = if self.request.widget
         {
+            //the widget holding the grab might be gone
+            self.pointer.grab = false;
             root_compute(self, data);
             let root = self.root.as_mut().unwrap();
             //"new data" after root rebuild because widgets rely on it being called before layout & paint
-            new_data_send(&mut self.request, &mut self.events, root, data);
+            broadcast(&mut self.request, &mut self.events, &mut self.focus, &mut self.timers, &mut self.pointer, self.modifiers.get(), root, data, event::WidgetEvent::NewData);
             root
         } else { root };
 
         //compute layout
-        let mut fits = true;
         if self.request.layout
         {
+            self.misfits.clear();
+            let mut path = Vec::new();
             let mut ctx = LayoutInquireCtx { painter: &mut self.painter };
             let min_size = root.layout_inquire(&mut ctx, data);
-            if min_size.0 < size.0 || min_size.1 < size.1 { fits = false; }
             //size logic
-            let mut ctx = LayoutComputeCtx { fits: &mut fits, painter: &mut self.painter };
+            let mut ctx = LayoutComputeCtx { misfits: &mut self.misfits, path: &mut path, painter: &mut self.painter, screen: size };
             root.layout_compute(&mut ctx, data, size);
+            //only when no widget reported it itself, e.g. a fixed size on the root
+            if ctx.misfits.is_empty() { ctx.does_not_fit("Ui", min_size, size); }
         }
 
         //compute painting
         if self.request.paint
         {
             self.painter.clear_frame(scale);
-            let mut ctx = PaintCtx { painter: &mut self.painter, style: &mut self.style, state: interact::WidgetState::Cold };
+            self.focus.clear_order();
+            self.hits.clear();
+            self.access.clear();
+            self.debug.clear();
+            self.ime_caret = None;
+            let mut ctx = PaintCtx { painter: &mut self.painter, style: &mut self.style, state: interact::WidgetState::Cold, screen: size, focus: &mut self.focus, focused: false, state_from: interact::WidgetState::Cold, state_blend: 1.0, time, hits: &mut self.hits, access: &mut self.access, debug: &mut self.debug, pointer: &mut self.pointer, ime_caret: &mut self.ime_caret, animate: &mut self.request.animate, ghost: false };
             root.paint(&mut ctx, data);
+            self.debug.paint(&mut self.painter, &self.hits, &self.misfits);
         }
 
         //return
+        self.animate = self.request.animate;
         self.request.reset();
         let events = &mut self.events;
         let paint = self.painter.get_frame();
         let request = &mut self.request;
-        Frame { px_per_ui_unit: scale, fits, events, paint, request }
+        let animate = self.animate;
+        let misfits = &self.misfits;
+        let fits = misfits.is_empty();
+        let next_deadline = match (self.timers.next_deadline(), self.touches.next_deadline())
+        {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }.map(|deadline| (deadline - time).max(0.0));
+        let (cursor, pointer_grab) = (self.pointer.cursor, self.pointer.grab);
+        let ime_caret = self.ime_caret.map(|rect| math::Rect { min: rect.min * scale, max: rect.max * scale });
+        Frame { px_per_ui_unit: scale, fits, misfits, animate, next_deadline, cursor, pointer_grab, ime_caret, events, paint, request }
     }
 }
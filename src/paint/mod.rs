@@ -1,4 +1,5 @@
 pub mod style;
+#[cfg(feature = "raster")] pub mod raster;
 
 use gru_misc::{math::{Vec2, Rect}, text_sdf::{Font, AtlasBuilder, Align, Layout}};
 
@@ -7,30 +8,207 @@ const TEXTURE_PADDING: u32 = 5;
 
 pub use gru_misc::color::Color;
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureId(u64);
+
+#[derive(Clone, Copy)]
+pub enum TexCoords
+{
+    None,
+    //sdf in the font page, the color is only masked by it
+    Glyph(f32, f32, u32),
+    //plain color texture registered on the ui, multiplied with the color
+    Texture(f32, f32, TextureId)
+}
+
+impl TexCoords
+{
+    fn uv(&self) -> Option<(f32, f32)>
+    {
+        match *self
+        {
+            Self::None => None,
+            Self::Glyph(u, v, _) | Self::Texture(u, v, _) => Some((u, v))
+        }
+    }
+
+    fn with_uv(self, u: f32, v: f32) -> Self
+    {
+        match self
+        {
+            Self::None => Self::None,
+            Self::Glyph(_, _, page) => Self::Glyph(u, v, page),
+            Self::Texture(_, _, id) => Self::Texture(u, v, id)
+        }
+    }
+}
+
 pub struct Vertex
 {
     pub position: Vec2,
     pub color: Color,
-    pub tex_coords: Option<(f32, f32, u32)>
+    pub tex_coords: TexCoords
+}
+
+#[derive(Clone, Copy)]
+pub struct DrawCommand
+{
+    pub clip: Option<Rect>,
+    pub first_index: u32,
+    pub index_count: u32,
+    //added to every index of the command, u16 indices cannot reach past 65535 vertices otherwise
+    //backends without base vertex draws (GLES2, WebGL) bind the vertex buffer at an offset of base_vertex vertices instead
+    pub base_vertex: u32
+}
+
+pub enum Indices<'a>
+{
+    U16(&'a [u16]),
+    U32(&'a [u32])
 }
 
 pub struct Frame<'a>
 {
     pub new: bool,
     pub vertices: &'a [Vertex],
-    pub indices: &'a [u16],
+    pub indices: Indices<'a>,
+    pub commands: &'a [DrawCommand],
     pub font_version: u64,
     pub font_data: &'a Vec<Vec<u8>>
 }
 
+impl<'a> Frame<'a>
+{
+    //indices into vertices, base vertex included
+    pub fn vertex_indices(&self, command: &DrawCommand) -> Vec<u32>
+    {
+        let range = command.first_index as usize..(command.first_index + command.index_count) as usize;
+        match self.indices
+        {
+            Indices::U16(indices) => indices[range].iter().map(|i| command.base_vertex + *i as u32).collect(),
+            Indices::U32(indices) => indices[range].iter().map(|i| command.base_vertex + *i).collect()
+        }
+    }
+}
+
+fn same_clip(a: Option<Rect>, b: Option<Rect>) -> bool
+{
+    match (a, b)
+    {
+        (None, None) => true,
+        (Some(a), Some(b)) => a.min == b.min && a.max == b.max,
+        _ => false
+    }
+}
+
+pub(crate) fn intersect(a: Rect, b: Rect) -> Rect
+{
+    Rect { min: Vec2(a.min.0.max(b.min.0), a.min.1.max(b.min.1)), max: Vec2(a.max.0.min(b.max.0), a.max.1.min(b.max.1)) }
+}
+
+struct Mesh
+{
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    commands: Vec<DrawCommand>
+}
+
+impl Mesh
+{
+    fn new() -> Self
+    {
+        Self { vertices: Vec::new(), indices: Vec::new(), commands: Vec::new() }
+    }
+
+    fn clear(&mut self)
+    {
+        self.vertices.clear();
+        self.indices.clear();
+        self.commands.clear();
+    }
+
+    //make sure the following indices end up in a command with the given clip
+    fn begin(&mut self, clip: Option<Rect>)
+    {
+        let first_index = self.indices.len() as u32;
+        if let Some(last) = self.commands.last_mut()
+        {
+            if same_clip(last.clip, clip) { return; }
+            if last.first_index == first_index
+            {
+                last.clip = clip;
+                return;
+            }
+        }
+        self.commands.push(DrawCommand { clip, first_index, index_count: 0, base_vertex: 0 });
+    }
+
+    fn finish(&mut self)
+    {
+        let mut end = self.indices.len() as u32;
+        for command in self.commands.iter_mut().rev()
+        {
+            command.index_count = end - command.first_index;
+            end = command.first_index;
+        }
+        self.commands.retain(|command| command.index_count > 0);
+    }
+
+    fn append(&mut self, other: &mut Mesh)
+    {
+        let i0 = self.vertices.len() as u32;
+        let first_index = self.indices.len() as u32;
+        self.commands.extend(other.commands.drain(..).map(|command| DrawCommand { first_index: command.first_index + first_index, ..command }));
+        self.indices.extend(other.indices.drain(..).map(|i| i0 + i));
+        self.vertices.append(&mut other.vertices);
+    }
+
+    //splits the commands wherever their vertices stop fitting into u16 relative to the base vertex of the batch
+    fn batch(&self, indices: &mut Vec<u16>, batches: &mut Vec<DrawCommand>)
+    {
+        indices.clear();
+        batches.clear();
+        for command in &self.commands
+        {
+            let range = command.first_index as usize..(command.first_index + command.index_count) as usize;
+            let mut batch: Option<DrawCommand> = None;
+            for triangle in self.indices[range].chunks_exact(3)
+            {
+                let (min, max) = (triangle.iter().copied().min().unwrap(), triangle.iter().copied().max().unwrap());
+                let fits = batch.is_some_and(|batch| min >= batch.base_vertex && max - batch.base_vertex <= u16::MAX as u32);
+                if !fits
+                {
+                    if let Some(batch) = batch { batches.push(batch); }
+                    batch = Some(DrawCommand { clip: command.clip, first_index: indices.len() as u32, index_count: 0, base_vertex: min });
+                }
+                let current = batch.as_mut().unwrap();
+                indices.extend(triangle.iter().map(|i| (i - current.base_vertex) as u16));
+                current.index_count += 3;
+            }
+            if let Some(batch) = batch { batches.push(batch); }
+        }
+    }
+}
+
 pub(crate) struct Painter
 {
     text_version: u64,
     text: Option<AtlasBuilder>,
     origin: Vec2,
     scale: f32,
-    vertices: Vec<Vertex>,
-    indices: Vec<u16>,
+    layers: Vec<Mesh>,
+    layer: usize,
+    top: usize,
+    layer_origins: Vec<(Vec2, Vec<Rect>, usize)>,
+    clips: Vec<Rect>,
+    cpu_clip: bool,
+    wide_indices: bool,
+    //u16 output of the flattened mesh
+    narrow_indices: Vec<u16>,
+    batches: Vec<DrawCommand>,
+    //pixel sizes of the user textures
+    textures: Vec<(TextureId, (u32, u32))>,
+    next_texture: u64,
     new: bool
 }
 
@@ -52,24 +230,148 @@ impl Painter
             text: Some(Self::atlas_builder(font, 1.0)),
             origin: Vec2(0.0, 0.0),
             scale: 1.0,
-            vertices: Vec::new(),
-            indices: Vec::new(),
+            layers: vec![Mesh::new()],
+            layer: 0,
+            top: 0,
+            layer_origins: Vec::new(),
+            clips: Vec::new(),
+            cpu_clip: false,
+            wide_indices: false,
+            narrow_indices: Vec::new(),
+            batches: Vec::new(),
+            textures: Vec::new(),
+            next_texture: 0,
             new: true
         }
     }
 
+    pub fn set_cpu_clip(&mut self, cpu_clip: bool)
+    {
+        self.cpu_clip = cpu_clip;
+    }
+
+    pub fn set_wide_indices(&mut self, wide_indices: bool)
+    {
+        self.wide_indices = wide_indices;
+    }
+
+    pub fn add_texture(&mut self, width: u32, height: u32) -> TextureId
+    {
+        let id = TextureId(self.next_texture);
+        self.next_texture += 1;
+        self.textures.push((id, (width, height)));
+        id
+    }
+
+    pub fn remove_texture(&mut self, id: TextureId)
+    {
+        self.textures.retain(|(other, _)| *other != id);
+    }
+
+    pub fn texture_size(&self, id: TextureId) -> Option<(u32, u32)>
+    {
+        self.textures.iter().find(|(other, _)| *other == id).map(|(_, size)| *size)
+    }
+
     pub fn add_offset(&mut self, offset: Vec2)
     {
         self.origin += offset;
     }
 
+    pub fn origin(&self) -> Vec2
+    {
+        self.origin
+    }
+
+    //everything drawn until the matching pop ends up on top of the current layer, unclipped by the layer below
+    pub fn push_layer(&mut self, origin: Vec2)
+    {
+        self.layer_origins.push((self.origin, std::mem::take(&mut self.clips), self.layer));
+        self.origin = origin;
+        self.layer += 1;
+        self.top = self.top.max(self.layer);
+        if self.layers.len() <= self.layer { self.layers.push(Mesh::new()); }
+    }
+
+    //above every layer used so far in this frame
+    pub fn push_top_layer(&mut self, origin: Vec2)
+    {
+        self.layer_origins.push((self.origin, std::mem::take(&mut self.clips), self.layer));
+        self.origin = origin;
+        self.top += 1;
+        self.layer = self.top;
+        if self.layers.len() <= self.layer { self.layers.push(Mesh::new()); }
+    }
+
+    pub fn pop_layer(&mut self)
+    {
+        (self.origin, self.clips, self.layer) = self.layer_origins.pop().unwrap();
+    }
+
+    pub fn push_clip(&mut self, rect: Rect)
+    {
+        let rect = Rect { min: self.origin + rect.min, max: self.origin + rect.max };
+        let rect = match self.clips.last() { Some(clip) => intersect(*clip, rect), None => rect };
+        self.clips.push(rect);
+    }
+
+    pub fn pop_clip(&mut self)
+    {
+        self.clips.pop();
+    }
+
+    //current clip in absolute ui units
+    pub fn clip_rect(&self) -> Option<Rect>
+    {
+        self.clips.last().copied()
+    }
+
+    #[inline] pub fn layer(&self) -> usize { self.layer }
+
+    //current clip in pixels
+    fn clip(&self) -> Option<Rect>
+    {
+        self.clips.last().map(|clip| Rect { min: clip.min * self.scale, max: clip.max * self.scale })
+    }
+
+    //clipping on the cpu cuts the vertices instead, so the commands carry no scissor
+    fn mesh(&mut self) -> &mut Mesh
+    {
+        let clip = if self.cpu_clip { None } else { self.clip() };
+        self.scissored_mesh(clip)
+    }
+
+    fn scissored_mesh(&mut self, clip: Option<Rect>) -> &mut Mesh
+    {
+        let mesh = &mut self.layers[self.layer];
+        mesh.begin(clip);
+        mesh
+    }
+
     pub fn draw_rect(&mut self, rect: Rect, color: Color)
     {
-        let min = self.origin + rect.min;
-        let max = self.origin + rect.max;
-        let i0 = self.vertices.len() as u16;
-        for pos in [min, Vec2(min.0, max.1), max, Vec2(max.0, min.1)] { self.vertices.push(Vertex { position: pos * self.scale, color, tex_coords: None }); }
-        for i in [0, 1, 2, 2, 3, 0] { self.indices.push(i0 + i); }
+        let scale = self.scale;
+        let mut min = (self.origin + rect.min) * scale;
+        let mut max = (self.origin + rect.max) * scale;
+        if self.cpu_clip && let Some(clip) = self.clip()
+        {
+            let rect = intersect(clip, Rect { min, max });
+            if rect.min.0 >= rect.max.0 || rect.min.1 >= rect.max.1 { return; }
+            (min, max) = (rect.min, rect.max);
+        }
+        let mesh = self.mesh();
+        let i0 = mesh.vertices.len() as u32;
+        for pos in [min, Vec2(min.0, max.1), max, Vec2(max.0, min.1)] { mesh.vertices.push(Vertex { position: pos, color, tex_coords: TexCoords::None }); }
+        for i in [0, 1, 2, 2, 3, 0] { mesh.indices.push(i0 + i); }
+    }
+
+    pub fn draw_outline(&mut self, rect: Rect, width: f32, color: Color)
+    {
+        let (min, max) = (rect.min, rect.max);
+        self.draw_rect(Rect { min, max: Vec2(max.0, min.1 + width) }, color);
+        self.draw_rect(Rect { min: Vec2(min.0, max.1 - width), max }, color);
+        self.draw_rect(Rect { min: Vec2(min.0, min.1 + width), max: Vec2(min.0 + width, max.1 - width) }, color);
+        self.draw_rect(Rect { min: Vec2(max.0 - width, min.1 + width), max: Vec2(max.0, max.1 - width) }, color);
     }
 
     pub fn draw_rhombus(&mut self, rect: Rect, color: Color)
@@ -77,31 +379,66 @@ impl Painter
         let min = self.origin + rect.min;
         let max = self.origin + rect.max;
         let size = rect.size();
-        let i0 = self.vertices.len() as u16;
+        let scale = self.scale;
+        //cutting a rhombus would not leave a quad, so on the cpu only the ones reaching over the clip keep the scissor
+        let mut scissor = None;
+        if self.cpu_clip && let Some(clip) = self.clip()
+        {
+            let (min, max) = (min * scale, max * scale);
+            let rect = intersect(clip, Rect { min, max });
+            if rect.min.0 >= rect.max.0 || rect.min.1 >= rect.max.1 { return; }
+            if rect.min != min || rect.max != max { scissor = Some(clip); }
+        }
+        let mesh = if self.cpu_clip { self.scissored_mesh(scissor) } else { self.mesh() };
+        let i0 = mesh.vertices.len() as u32;
         for pos in
         [
             Vec2(min.0, min.1 + size.1 / 2.0), //left
             Vec2(min.0 + size.0 / 2.0, max.1), //bottom
             Vec2(max.0, min.1 + size.1 / 2.0), //right
             Vec2(min.0 + size.0 / 2.0, min.1) //top
-        ] { self.vertices.push(Vertex { position: pos * self.scale, color, tex_coords: None }); }
-        for i in [0, 1, 2, 2, 3, 0] { self.indices.push(i0 + i); }
+        ] { mesh.vertices.push(Vertex { position: pos * scale, color, tex_coords: TexCoords::None }); }
+        for i in [0, 1, 2, 2, 3, 0] { mesh.indices.push(i0 + i); }
     }
 
     pub fn draw_text(&mut self, rect: Rect, text: &str, size: f32, align: Align, auto_wrap: bool, color: Color)
     {
         self.add_glyphs(text);
-        let atlas_builder = self.text.as_mut().unwrap();
         let width = (rect.max.0 - rect.min.0) / size;
         let offset = self.origin + rect.min + Vec2(0.0, (rect.max.1 - rect.min.1 - size) / 2.0);
-        let i0 = self.vertices.len() as u16;
+        let scale = self.scale;
+        let clip = if self.cpu_clip { self.clip() } else { None };
+        self.mesh();
+        let atlas_builder = self.text.as_mut().unwrap();
+        let mesh = &mut self.layers[self.layer];
+        let i0 = mesh.vertices.len() as u32;
         atlas_builder.atlas().text
         (
             text,
             Layout { width, align, auto_wrap },
-            |index| self.indices.push(i0 + index as u16),
-            |tex_coords, position| self.vertices.push(Vertex { position: (Vec2::from(position) * size + offset) * self.scale, color, tex_coords: Some(tex_coords) })
+            |index| mesh.indices.push(i0 + index as u32),
+            |(u, v, page), position| mesh.vertices.push(Vertex { position: (Vec2::from(position) * size + offset) * scale, color, tex_coords: TexCoords::Glyph(u, v, page) })
         );
+        if let Some(clip) = clip { for glyph in mesh.vertices[i0 as usize..].chunks_exact_mut(4) { clip_quad(glyph, clip); } }
+    }
+
+    //uv in 0..1 of the texture
+    pub fn draw_image(&mut self, rect: Rect, texture: TextureId, uv: Rect, tint: Color)
+    {
+        let scale = self.scale;
+        let (min, max) = ((self.origin + rect.min) * scale, (self.origin + rect.max) * scale);
+        let clip = if self.cpu_clip { self.clip() } else { None };
+        let mesh = self.mesh();
+        let i0 = mesh.vertices.len() as u32;
+        for (pos, (u, v)) in
+        [
+            (min, (uv.min.0, uv.min.1)),
+            (Vec2(min.0, max.1), (uv.min.0, uv.max.1)),
+            (max, (uv.max.0, uv.max.1)),
+            (Vec2(max.0, min.1), (uv.max.0, uv.min.1))
+        ] { mesh.vertices.push(Vertex { position: pos, color: tint, tex_coords: TexCoords::Texture(u, v, texture) }); }
+        for i in [0, 1, 2, 2, 3, 0] { mesh.indices.push(i0 + i); }
+        if let Some(clip) = clip { clip_quad(&mut mesh.vertices[i0 as usize..], clip); }
     }
 
     pub fn clear_frame(&mut self, scale: f32)
@@ -113,8 +450,11 @@ impl Painter
         }
         self.origin = Vec2(0.0, 0.0);
         self.scale = scale;
-        self.vertices.clear();
-        self.indices.clear();
+        for mesh in &mut self.layers { mesh.clear(); }
+        self.layer = 0;
+        self.top = 0;
+        self.layer_origins.clear();
+        self.clips.clear();
         self.new = true;
     }
 
@@ -122,7 +462,17 @@ impl Painter
     {
         let new = self.new;
         self.new = false;
-        Frame { new, vertices: &self.vertices, indices: &self.indices, font_version: self.text_version, font_data: self.text.as_ref().unwrap().sdf() }
+        if new
+        {
+            //flatten the layers in drawing order
+            for mesh in &mut self.layers { mesh.finish(); }
+            let (base, layers) = self.layers.split_first_mut().unwrap();
+            for mesh in layers { base.append(mesh); }
+            if !self.wide_indices { base.batch(&mut self.narrow_indices, &mut self.batches); }
+        }
+        let mesh = &self.layers[0];
+        let (indices, commands) = if self.wide_indices { (Indices::U32(&mesh.indices), &mesh.commands[..]) } else { (Indices::U16(&self.narrow_indices), &self.batches[..]) };
+        Frame { new, vertices: &mesh.vertices, indices, commands, font_version: self.text_version, font_data: self.text.as_ref().unwrap().sdf() }
     }
 
     pub fn text_width(&mut self, text: &str, size: f32) -> f32
@@ -142,3 +492,26 @@ impl Painter
         if self.text.as_mut().unwrap().add(text.chars()) { self.text_version += 1; }
     }
 }
+
+//glyph and image quads are axis aligned with texture coordinates linear in the position, so clamping the corners and shifting their texture coordinates along cuts the quad
+fn clip_quad(quad: &mut [Vertex], clip: Rect)
+{
+    let a = &quad[0];
+    let (Some((u0, v0)), position) = (a.tex_coords.uv(), a.position) else { return; };
+    let mut du = 0.0;
+    let mut dv = 0.0;
+    for vertex in &quad[1..]
+    {
+        let Some((u, v)) = vertex.tex_coords.uv() else { return; };
+        if vertex.position.0 != position.0 { du = (u - u0) / (vertex.position.0 - position.0); }
+        if vertex.position.1 != position.1 { dv = (v - v0) / (vertex.position.1 - position.1); }
+    }
+    for vertex in quad
+    {
+        let Some((u, v)) = vertex.tex_coords.uv() else { continue; };
+        let x = vertex.position.0.max(clip.min.0).min(clip.max.0);
+        let y = vertex.position.1.max(clip.min.1).min(clip.max.1);
+        vertex.tex_coords = vertex.tex_coords.with_uv(u + (x - vertex.position.0) * du, v + (y - vertex.position.1) * dv);
+        vertex.position = Vec2(x, y);
+    }
+}
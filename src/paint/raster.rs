@@ -0,0 +1,205 @@
+use super::{Frame, Vertex, Color, TexCoords, TextureId, TEXTURE_SIZE};
+use gru_misc::math::{Vec2, Rect};
+use std::{io::{self, Write}, fs::File, path::Path};
+
+const SDF_EDGE: f32 = 0.5;
+const SDF_SMOOTHING: f32 = 0.1;
+
+pub struct RgbaImage
+{
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage
+{
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4]
+    {
+        let i = (y * self.width + x) as usize * 4;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()>
+    {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&self.pixels).map_err(io::Error::other)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()>
+    {
+        self.write_png(io::BufWriter::new(File::create(path)?))
+    }
+}
+
+struct Target
+{
+    width: u32,
+    height: u32,
+    //linear rgba, straight alpha
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Target
+{
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 4])
+    {
+        let dst = &mut self.pixels[(y * self.width + x) as usize];
+        let a = color[3];
+        let out_a = a + dst[3] * (1.0 - a);
+        if out_a <= 0.0 { return; }
+        for c in 0..3 { dst[c] = (color[c] * a + dst[c] * dst[3] * (1.0 - a)) / out_a; }
+        dst[3] = out_a;
+    }
+
+    fn into_image(self) -> RgbaImage
+    {
+        let mut pixels = Vec::with_capacity(self.pixels.len() * 4);
+        for [r, g, b, a] in self.pixels
+        {
+            pixels.push(linear_to_srgb(r));
+            pixels.push(linear_to_srgb(g));
+            pixels.push(linear_to_srgb(b));
+            pixels.push((a.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+        RgbaImage { width: self.width, height: self.height, pixels }
+    }
+}
+
+fn srgb_to_linear(c: u8) -> f32
+{
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(c: f32) -> u8
+{
+    let c = c.clamp(0.0, 1.0);
+    let s = if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    (s * 255.0).round() as u8
+}
+
+fn color_to_array(color: Color) -> [f32; 4]
+{
+    [color.r, color.g, color.b, color.a]
+}
+
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32
+{
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+//top-left fill rule, so that shared edges of adjacent triangles are not blended twice
+fn is_top_left(a: Vec2, b: Vec2) -> bool
+{
+    (a.1 == b.1 && b.0 < a.0) || b.1 < a.1
+}
+
+fn sample_sdf(page: &[u8], u: f32, v: f32) -> f32
+{
+    let size = TEXTURE_SIZE as i32;
+    let x = u * TEXTURE_SIZE as f32 - 0.5;
+    let y = v * TEXTURE_SIZE as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let texel = |x: i32, y: i32| page[(y.clamp(0, size - 1) * size + x.clamp(0, size - 1)) as usize] as f32 / 255.0;
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let top = texel(x0, y0) * (1.0 - fx) + texel(x0 + 1, y0) * fx;
+    let bottom = texel(x0, y0 + 1) * (1.0 - fx) + texel(x0 + 1, y0 + 1) * fx;
+    top * (1.0 - fy) + bottom * fy
+}
+
+//nearest texel, linear rgba
+fn sample_texture(image: &RgbaImage, u: f32, v: f32) -> [f32; 4]
+{
+    let x = ((u * image.width as f32) as i64).clamp(0, image.width as i64 - 1) as u32;
+    let y = ((v * image.height as f32) as i64).clamp(0, image.height as i64 - 1) as u32;
+    let [r, g, b, a] = image.pixel(x, y);
+    [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a as f32 / 255.0]
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32
+{
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn draw_triangle(target: &mut Target, font_data: &[Vec<u8>], textures: &[(TextureId, &RgbaImage)], clip: Option<Rect>, mut v: [&Vertex; 3])
+{
+    let mut area = edge(v[0].position, v[1].position, v[2].position);
+    if area == 0.0 { return; }
+    if area < 0.0
+    {
+        v.swap(1, 2);
+        area = -area;
+    }
+    let (p0, p1, p2) = (v[0].position, v[1].position, v[2].position);
+    let mut min_x = p0.0.min(p1.0).min(p2.0).floor().max(0.0) as u32;
+    let mut min_y = p0.1.min(p1.1).min(p2.1).floor().max(0.0) as u32;
+    let mut max_x = (p0.0.max(p1.0).max(p2.0).ceil().max(0.0) as u32).min(target.width);
+    let mut max_y = (p0.1.max(p1.1).max(p2.1).ceil().max(0.0) as u32).min(target.height);
+    //scissor like a gpu would, on whole pixels
+    if let Some(clip) = clip
+    {
+        min_x = min_x.max(clip.min.0.round().max(0.0) as u32);
+        min_y = min_y.max(clip.min.1.round().max(0.0) as u32);
+        max_x = max_x.min(clip.max.0.round().max(0.0) as u32);
+        max_y = max_y.min(clip.max.1.round().max(0.0) as u32);
+    }
+    let top_left = [is_top_left(p1, p2), is_top_left(p2, p0), is_top_left(p0, p1)];
+    let colors = [color_to_array(v[0].color), color_to_array(v[1].color), color_to_array(v[2].color)];
+    for y in min_y..max_y
+    {
+        for x in min_x..max_x
+        {
+            let p = Vec2(x as f32 + 0.5, y as f32 + 0.5);
+            let w = [edge(p1, p2, p), edge(p2, p0, p), edge(p0, p1, p)];
+            if (0..3).any(|i| w[i] < 0.0 || (w[i] == 0.0 && !top_left[i])) { continue; }
+            let b = [w[0] / area, w[1] / area, w[2] / area];
+            let mut color = [0.0; 4];
+            for c in 0..4 { color[c] = colors[0][c] * b[0] + colors[1][c] * b[1] + colors[2][c] * b[2]; }
+            match (v[0].tex_coords, v[1].tex_coords, v[2].tex_coords)
+            {
+                (TexCoords::Glyph(u0, v0, page), TexCoords::Glyph(u1, v1, _), TexCoords::Glyph(u2, v2, _)) =>
+                {
+                    let u = u0 * b[0] + u1 * b[1] + u2 * b[2];
+                    let v = v0 * b[0] + v1 * b[1] + v2 * b[2];
+                    let distance = font_data.get(page as usize).map_or(0.0, |page| sample_sdf(page, u, v));
+                    color[3] *= smoothstep(SDF_EDGE - SDF_SMOOTHING, SDF_EDGE + SDF_SMOOTHING, distance);
+                },
+                //unknown textures leave the tint
+                (TexCoords::Texture(u0, v0, id), TexCoords::Texture(u1, v1, _), TexCoords::Texture(u2, v2, _)) => if let Some((_, image)) = textures.iter().find(|(other, _)| *other == id)
+                {
+                    let texel = sample_texture(image, u0 * b[0] + u1 * b[1] + u2 * b[2], v0 * b[0] + v1 * b[1] + v2 * b[2]);
+                    for c in 0..4 { color[c] *= texel[c]; }
+                },
+                _ => {}
+            }
+            target.blend(x, y, color);
+        }
+    }
+}
+
+pub fn rasterize(frame: &Frame, width: u32, height: u32) -> RgbaImage
+{
+    rasterize_textured(frame, width, height, &[])
+}
+
+//with the pixels of the textures registered on the ui
+pub fn rasterize_textured(frame: &Frame, width: u32, height: u32, textures: &[(TextureId, &RgbaImage)]) -> RgbaImage
+{
+    let mut target = Target { width, height, pixels: vec![[0.0; 4]; (width * height) as usize] };
+    for command in frame.commands
+    {
+        let indices = frame.vertex_indices(command);
+        for triangle in indices.chunks_exact(3)
+        {
+            let vertex = |i: usize| &frame.vertices[triangle[i] as usize];
+            draw_triangle(&mut target, frame.font_data, textures, command.clip, [vertex(0), vertex(1), vertex(2)]);
+        }
+    }
+    target.into_image()
+}
@@ -1,5 +1,5 @@
 use super::Color;
-use crate::widget::interact::WidgetState;
+use crate::{widget::interact::WidgetState, anim::Lerp};
 
 #[derive(Clone)]
 pub struct ColorSet
@@ -20,6 +20,11 @@ impl ColorSet
             WidgetState::Hot => self.hot,
         }
     }
+
+    pub fn blend(&self, from: WidgetState, to: WidgetState, t: f32) -> Color
+    {
+        self.get(from).lerp(self.get(to), t)
+    }
 }
 
 #[derive(Clone)]
@@ -29,6 +34,7 @@ pub struct StyleSet
     pub top: Color,
     pub text: Color,
     pub data: ColorSet,
+    pub focus: Color,
 }
 
 impl Default for StyleSet
@@ -51,6 +57,7 @@ impl Default for StyleSet
                 hover: Color::from_discrete_srgb(50, 150, 50, 255),
                 hot: Color::from_discrete_srgb(250, 200, 200, 255),
             },
+            focus: Color::from_discrete_srgb(250, 200, 50, 255),
         }
     }
 }
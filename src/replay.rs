@@ -0,0 +1,36 @@
+use crate::{Ui, UiConfig, Frame, math::Vec2, event::HardwareEvent};
+use serde::{Serialize, Deserialize};
+
+//gru_misc does not know about serde
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Vec2")]
+pub(crate) struct Vec2Def(pub f32, pub f32);
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecordedFrame
+{
+    pub config: UiConfig,
+    pub dt: f32,
+    pub events: Vec<HardwareEvent>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Recording
+{
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    //feeds every recorded frame to the ui, which should be freshly constructed from the same data as the recorded one
+    //only frames are recorded, calls on the ui in between (shortcut, add_texture, request, set_focus, set_debug) have to be repeated by the caller
+    pub fn replay<'a, T: 'a, E>(&self, ui: &mut Ui<'a, T, E>, data: &mut T, mut f: impl FnMut(Frame<'_, E>))
+    {
+        for frame in &self.frames { f(ui.frame(frame.config.clone(), data, frame.dt, frame.events.iter())); }
+    }
+}
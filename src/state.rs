@@ -0,0 +1,94 @@
+use std::{any::Any, cell::Cell, collections::HashMap, hash::{DefaultHasher, Hash, Hasher}, rc::Rc};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidgetId(u64);
+
+impl WidgetId
+{
+    pub fn new(key: impl Hash) -> Self
+    {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+pub struct Persistent<S: Copy>(Rc<Cell<S>>);
+
+impl<S: Copy> Clone for Persistent<S>
+{
+    fn clone(&self) -> Self
+    {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: Copy> Persistent<S>
+{
+    pub fn new(value: S) -> Self
+    {
+        Self(Rc::new(Cell::new(value)))
+    }
+
+    #[inline] pub fn get(&self) -> S { self.0.get() }
+    #[inline] pub fn set(&self, value: S) { self.0.set(value); }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum StateKey
+{
+    Id(WidgetId),
+    Position(usize),
+}
+
+struct Entry
+{
+    state: Box<dyn Any>,
+    used: bool,
+}
+
+pub(crate) struct StateStore
+{
+    entries: HashMap<StateKey, Entry>,
+    position: usize,
+}
+
+impl StateStore
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { entries: HashMap::new(), position: 0 }
+    }
+
+    pub(crate) fn begin(&mut self)
+    {
+        self.position = 0;
+        for entry in self.entries.values_mut() { entry.used = false; }
+    }
+
+    //states not asked for during the last root construction belong to widgets that are gone
+    pub(crate) fn end(&mut self)
+    {
+        self.entries.retain(|_, entry| entry.used);
+    }
+
+    fn get<S: Copy + Default + 'static>(&mut self, key: StateKey) -> Persistent<S>
+    {
+        let entry = self.entries.entry(key).or_insert_with(|| Entry { state: Box::new(Persistent::new(S::default())), used: false });
+        if !entry.state.is::<Persistent<S>>() { entry.state = Box::new(Persistent::new(S::default())); }
+        entry.used = true;
+        entry.state.downcast_ref::<Persistent<S>>().unwrap().clone()
+    }
+
+    pub(crate) fn with_id<S: Copy + Default + 'static>(&mut self, id: WidgetId) -> Persistent<S>
+    {
+        self.get(StateKey::Id(id))
+    }
+
+    pub(crate) fn next<S: Copy + Default + 'static>(&mut self) -> Persistent<S>
+    {
+        let key = StateKey::Position(self.position);
+        self.position += 1;
+        self.get(key)
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{Ui, UiInit, UiConfig, Frame, Widget, WidgetComputeCtx, math::Vec2, text::Font, paint::{self, Color}, event::{HardwareEvent, MouseButton, Key, KeyChord, TouchPhase}};
+use std::{fmt::Write, fs, path::Path};
+
+const UPDATE_ENV: &str = "GRU_UI_UPDATE_SNAPSHOTS";
+const DEFAULT_DT: f32 = 1.0 / 60.0;
+
+pub struct Harness<'a, T: 'a, E>
+{
+    ui: Ui<'a, T, E>,
+    config: UiConfig,
+    dt: f32,
+    events: Vec<HardwareEvent>,
+}
+
+impl<'a, T: 'a, E> Harness<'a, T, E>
+{
+    pub fn new<W: FnMut(&mut WidgetComputeCtx, &mut T) -> Box<dyn Widget<T, E> + 'a> + 'a>(font: Font, size: Vec2, constructor: W) -> Self
+    {
+        Self::with_init(UiInit::new(font), size, constructor)
+    }
+
+    //e.g. for cpu clipping or wide indices
+    pub fn with_init<W: FnMut(&mut WidgetComputeCtx, &mut T) -> Box<dyn Widget<T, E> + 'a> + 'a>(init: UiInit, size: Vec2, constructor: W) -> Self
+    {
+        let ui = Ui::new(init, constructor);
+        let config = UiConfig { size, scale: 1.0, display_scale_factor: 1.0 };
+        Self { ui, config, dt: DEFAULT_DT, events: Vec::new() }
+    }
+
+    pub fn ui(&mut self) -> &mut Ui<'a, T, E>
+    {
+        &mut self.ui
+    }
+
+    pub fn config(&mut self) -> &mut UiConfig
+    {
+        &mut self.config
+    }
+
+    pub fn dt(&mut self) -> &mut f32
+    {
+        &mut self.dt
+    }
+
+    pub fn event(&mut self, event: HardwareEvent) -> &mut Self
+    {
+        self.events.push(event);
+        self
+    }
+
+    pub fn pointer_move(&mut self, pos: Vec2) -> &mut Self
+    {
+        self.event(HardwareEvent::PointerMoved { pos, delta: Vec2::zero() })
+    }
+
+    pub fn click(&mut self, pos: Vec2, button: MouseButton) -> &mut Self
+    {
+        self.pointer_move(pos);
+        self.event(HardwareEvent::PointerClicked { pos, button, pressed: true });
+        self.event(HardwareEvent::PointerClicked { pos, button, pressed: false })
+    }
+
+    pub fn touch(&mut self, id: u64, phase: TouchPhase, pos: Vec2) -> &mut Self
+    {
+        self.event(HardwareEvent::Touch { id, phase, pos })
+    }
+
+    pub fn key(&mut self, key: Key) -> &mut Self
+    {
+        self.event(HardwareEvent::Key { key, pressed: true });
+        self.event(HardwareEvent::Key { key, pressed: false })
+    }
+
+    pub fn chord(&mut self, chord: KeyChord) -> &mut Self
+    {
+        let modifiers = [(chord.modifiers.ctrl, Key::LControl), (chord.modifiers.shift, Key::LShift), (chord.modifiers.alt, Key::LAlt)];
+        for (held, key) in modifiers { if held { self.event(HardwareEvent::Key { key, pressed: true }); } }
+        self.key(chord.key);
+        for (held, key) in modifiers { if held { self.event(HardwareEvent::Key { key, pressed: false }); } }
+        self
+    }
+
+    pub fn text(&mut self, text: &str) -> &mut Self
+    {
+        for ch in text.chars() { self.event(HardwareEvent::Char(ch)); }
+        self
+    }
+
+    pub fn frame(&mut self, data: &mut T) -> Frame<'_, E>
+    {
+        let events = std::mem::take(&mut self.events);
+        self.ui.frame(self.config.clone(), data, self.dt, events.iter())
+    }
+
+    pub fn snapshot(&mut self, data: &mut T) -> String
+    {
+        let frame = self.frame(data);
+        let mut dump = format!("fits {}\n", frame.fits);
+        for misfit in frame.misfits
+        {
+            write!(dump, "misfit /{} {}", misfit.path, misfit.name).unwrap();
+            write_bounds(&mut dump, misfit.requested, misfit.available);
+            dump.push('\n');
+        }
+        dump.push_str(&dump_paint(&frame.paint));
+        dump
+    }
+
+    #[cfg(feature = "raster")]
+    pub fn image(&mut self, data: &mut T) -> paint::raster::RgbaImage
+    {
+        let (width, height) = (self.config.size.0 as u32, self.config.size.1 as u32);
+        let frame = self.frame(data);
+        paint::raster::rasterize(&frame.paint, width, height)
+    }
+}
+
+fn write_color(dump: &mut String, color: Color)
+{
+    write!(dump, " ({:.3} {:.3} {:.3} {:.3})", color.r, color.g, color.b, color.a).unwrap();
+}
+
+fn write_bounds(dump: &mut String, min: Vec2, max: Vec2)
+{
+    write!(dump, " {:.2} {:.2} {:.2} {:.2}", min.0, min.1, max.0, max.1).unwrap();
+}
+
+//every primitive of the painter is a quad of 4 vertices and 6 indices, consecutive glyph quads are merged into one text entry
+pub fn dump_paint(frame: &paint::Frame) -> String
+{
+    let mut dump = String::new();
+    let mut text: Option<(Vec2, Vec2, usize, Color)> = None;
+    let flush_text = |dump: &mut String, text: &mut Option<(Vec2, Vec2, usize, Color)>|
+    {
+        if let Some((min, max, glyphs, color)) = text.take()
+        {
+            dump.push_str("text");
+            write_bounds(dump, min, max);
+            write!(dump, " x{}", glyphs).unwrap();
+            write_color(dump, color);
+            dump.push('\n');
+        }
+    };
+    let mut clipped = false;
+    for command in frame.commands
+    {
+        flush_text(&mut dump, &mut text);
+        match command.clip
+        {
+            Some(clip) =>
+            {
+                dump.push_str("clip");
+                write_bounds(&mut dump, clip.min, clip.max);
+                dump.push('\n');
+                clipped = true;
+            },
+            None => if clipped
+            {
+                dump.push_str("unclip\n");
+                clipped = false;
+            },
+        }
+        let indices = frame.vertex_indices(command);
+        for quad in indices.chunks(6)
+        {
+            let i0 = quad.iter().copied().min().unwrap_or(0) as usize;
+            let vertices = &frame.vertices[i0..(i0 + 4).min(frame.vertices.len())];
+            let mut min = vertices[0].position;
+            let mut max = vertices[0].position;
+            for vertex in vertices
+            {
+                min = Vec2(min.0.min(vertex.position.0), min.1.min(vertex.position.1));
+                max = Vec2(max.0.max(vertex.position.0), max.1.max(vertex.position.1));
+            }
+            let color = vertices[0].color;
+            if let paint::TexCoords::Texture(..) = vertices[0].tex_coords
+            {
+                flush_text(&mut dump, &mut text);
+                dump.push_str("image");
+                write_bounds(&mut dump, min, max);
+                write_color(&mut dump, color);
+                dump.push('\n');
+                continue;
+            }
+            if let paint::TexCoords::Glyph(..) = vertices[0].tex_coords
+            {
+                text = Some(match text.take()
+                {
+                    Some((text_min, text_max, glyphs, text_color)) => (Vec2(text_min.0.min(min.0), text_min.1.min(min.1)), Vec2(text_max.0.max(max.0), text_max.1.max(max.1)), glyphs + 1, text_color),
+                    None => (min, max, 1, color),
+                });
+                continue;
+            }
+            flush_text(&mut dump, &mut text);
+            dump.push_str(if vertices[0].position.0 == vertices[1].position.0 { "rect" } else { "rhombus" });
+            write_bounds(&mut dump, min, max);
+            write_color(&mut dump, color);
+            dump.push('\n');
+        }
+    }
+    flush_text(&mut dump, &mut text);
+    dump
+}
+
+fn update_requested() -> bool
+{
+    std::env::var_os(UPDATE_ENV).is_some()
+}
+
+//a missing snapshot fails like a differing one, so a fresh checkout cannot pass by writing its own
+fn missing(path: &Path) -> !
+{
+    panic!("snapshot {} is missing (set {} to create it)", path.display(), UPDATE_ENV);
+}
+
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str)
+{
+    let path = path.as_ref();
+    if update_requested()
+    {
+        if let Some(dir) = path.parent() { fs::create_dir_all(dir).unwrap(); }
+        fs::write(path, actual).unwrap();
+        return;
+    }
+    if !path.exists() { missing(path); }
+    let expected = fs::read_to_string(path).unwrap();
+    if expected == actual { return; }
+    let line = expected.lines().zip(actual.lines()).position(|(e, a)| e != a).unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    panic!
+    (
+        "snapshot {} differs at line {}:\nexpected: {}\n  actual: {}\n(set {} to update)",
+        path.display(), line + 1,
+        expected.lines().nth(line).unwrap_or("<end>"),
+        actual.lines().nth(line).unwrap_or("<end>"),
+        UPDATE_ENV
+    );
+}
+
+#[cfg(feature = "raster")]
+pub fn assert_image_snapshot(path: impl AsRef<Path>, actual: &paint::raster::RgbaImage)
+{
+    let path = path.as_ref();
+    if update_requested()
+    {
+        if let Some(dir) = path.parent() { fs::create_dir_all(dir).unwrap(); }
+        actual.save_png(path).unwrap();
+        return;
+    }
+    if !path.exists() { missing(path); }
+    let decoder = png::Decoder::new(fs::File::open(path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let mut expected = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut expected).unwrap();
+    expected.truncate(info.buffer_size());
+    if info.width == actual.width && info.height == actual.height && expected == actual.pixels { return; }
+    let failed = path.with_extension("actual.png");
+    actual.save_png(&failed).unwrap();
+    panic!("image snapshot {} differs, actual image written to {} (set {} to update)", path.display(), failed.display(), UPDATE_ENV);
+}
@@ -0,0 +1,45 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+pub(crate) struct Timers
+{
+    next: u64,
+    //(deadline in ui time, id)
+    pending: Vec<(f32, TimerId)>,
+}
+
+impl Timers
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { next: 0, pending: Vec::new() }
+    }
+
+    pub(crate) fn schedule(&mut self, deadline: f32) -> TimerId
+    {
+        let id = TimerId(self.next);
+        self.next += 1;
+        self.pending.push((deadline, id));
+        id
+    }
+
+    pub(crate) fn cancel(&mut self, id: TimerId)
+    {
+        self.pending.retain(|(_, other)| *other != id);
+    }
+
+    //due timers in the order of their deadlines
+    pub(crate) fn take_due(&mut self, time: f32) -> Vec<TimerId>
+    {
+        let mut due: Vec<(f32, TimerId)> = self.pending.iter().copied().filter(|(deadline, _)| *deadline <= time).collect();
+        if due.is_empty() { return Vec::new(); }
+        self.pending.retain(|(deadline, _)| *deadline > time);
+        due.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        due.into_iter().map(|(_, id)| id).collect()
+    }
+
+    pub(crate) fn next_deadline(&self) -> Option<f32>
+    {
+        self.pending.iter().map(|(deadline, _)| *deadline).min_by(f32::total_cmp)
+    }
+}
@@ -1,8 +1,14 @@
 use super::*;
+use crate::anim::Easing;
 
-#[derive(Clone, Copy, PartialEq)]
+const TRANSITION_TIME: f32 = 0.15;
+const DRAG_THRESHOLD: f32 = 0.5;
+const DROP_WIDTH: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Default)]
 pub enum WidgetState
 {
+    #[default]
     Cold,
     Hot,
     Hover,
@@ -12,17 +18,77 @@ pub struct Response<'a, T, E: Clone, W: Widget<T, E>>
 {
     child: W,
     size: Vec2,
-    state: WidgetState,
+    state: Persistent<WidgetState>,
     action: Option<Box<dyn FnMut(&mut Request, &mut T) + 'a>>,
     event: Option<E>,
+    focus: Option<WidgetId>,
+    focused: bool,
+    state_from: WidgetState,
+    //start of the transition from state_from, in ui time
+    transition: f32,
 }
 
 impl<'a, T, E: Clone, W: Widget<T, E>> Widget<T, E> for Response<'a, T, E, W>
 {
     #[inline]
     fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        self.sync_focus(ctx, data);
+        //the focused widget handles keys and text in the focus pass and does not see them again afterwards
+        if let WidgetEvent::Focus(_) = ctx.event
+        {
+            if !self.focused
+            {
+                self.child.event(ctx, data);
+                return;
+            }
+            ctx.event = std::mem::replace(&mut ctx.event, WidgetEvent::NewData).into_hardware();
+            self.respond(ctx, data);
+            ctx.event = std::mem::replace(&mut ctx.event, WidgetEvent::NewData).into_focus();
+            return;
+        }
+        if let WidgetEvent::Hardware(event) = &ctx.event && self.focused && event.event.targets_focus() { return; }
+        self.respond(ctx, data);
+    }
+
+    impl_layout_inquire_child!(T);
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
+    {
+        self.size = self.child.layout_compute(ctx, data, size);
+        self.size
+    }
+
+    #[inline]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        ctx.record("Response", self.focus, self.size, true);
+        if let Some(id) = self.focus { ctx.focusable(id, self.size); }
+        let (old_state, old_from, old_blend, old_focused) = (ctx.state, ctx.state_from, ctx.state_blend, ctx.focused);
+        ctx.state = self.state.get();
+        ctx.state_from = self.state_from;
+        //driven by the paint time, so wrappers that filter NewData cannot freeze it
+        let progress = self.progress(ctx.time());
+        if progress < 1.0 { ctx.animate(); }
+        ctx.state_blend = Easing::EaseInOut.apply(progress);
+        ctx.focused = self.focused;
+        ctx.access_push(Role::Button, self.size);
+        self.child.paint(ctx, data);
+        ctx.access_pop();
+        ctx.state = old_state;
+        ctx.state_from = old_from;
+        ctx.state_blend = old_blend;
+        ctx.focused = old_focused;
+    }
+}
+
+impl<'a, T, E: Clone, W: Widget<T, E>> Response<'a, T, E, W>
+{
+    fn respond(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
     {
         self.child.event(ctx, data);
+        let time = ctx.time();
         let mut update = false;
         if let WidgetEvent::Hardware(event) = &mut ctx.event
         {
@@ -30,20 +96,20 @@ impl<'a, T, E: Clone, W: Widget<T, E>> Widget<T, E> for Response<'a, T, E, W>
             {
                 HardwareEvent::PointerGone =>
                 {
-                    self.state = WidgetState::Cold;
+                    self.set_state(WidgetState::Cold, time);
                     update = true;
                 },
                 HardwareEvent::PointerMoved { pos, .. } =>
                 {
                     let hover = Rect::new_origin(self.size).contains_linf(pos);
-                    if !hover && self.state != WidgetState::Cold
+                    if !hover && self.state.get() != WidgetState::Cold
                     {
-                        self.state = WidgetState::Cold;
+                        self.set_state(WidgetState::Cold, time);
                         update = true;
                     }
-                    if hover && self.state == WidgetState::Cold
+                    if hover && self.state.get() == WidgetState::Cold
                     {
-                        self.state = WidgetState::Hover;
+                        self.set_state(WidgetState::Hover, time);
                         update = true;
                     }
                 },
@@ -53,36 +119,36 @@ impl<'a, T, E: Clone, W: Widget<T, E>> Widget<T, E> for Response<'a, T, E, W>
                     let hover = Rect::new_origin(self.size).contains_linf(pos);
                     if hover && pressed && !event.used
                     {
-                        self.state = WidgetState::Hot;
+                        self.set_state(WidgetState::Hot, time);
                         update = true;
                         event.used = true;
-                    }
-                    if hover && self.state == WidgetState::Hot && !pressed
+                        if self.focus.is_some() { ctx.focus.set(self.focus); }
+                    } else if pressed && self.focused && ctx.focus.current() == self.focus { ctx.focus.set(None); }
+                    if hover && self.state.get() == WidgetState::Hot && !pressed
                     {
-                        self.state = WidgetState::Hover;
+                        self.set_state(WidgetState::Hover, time);
                         update = true;
                         event.used = true;
                         maybe_button = Some(button);
                     }
-                    if !pressed
-                    {
-                        let mut synthetic_event = WidgetEvent::Synthetic(SyntheticEvent::Clicked(maybe_button));
-                        std::mem::swap(&mut ctx.event, &mut synthetic_event);
-                        self.child.event(ctx, data);
-                        std::mem::swap(&mut ctx.event, &mut synthetic_event);
-                    }
-                    if maybe_button.is_some()
+                    if !pressed { self.click(ctx, data, maybe_button); }
+                },
+                //confirm acts like a primary click on the focused widget
+                HardwareEvent::Key { key: Key::Return, pressed } | HardwareEvent::GamepadButton { button: GamepadButton::South, pressed } if self.focused && !event.used =>
+                {
+                    event.used = true;
+                    update = true;
+                    if pressed { self.set_state(WidgetState::Hot, time); }
+                    else if self.state.get() == WidgetState::Hot
                     {
-                        if let Some(action) = &mut self.action { action(ctx.request, data); }
-                        if let Some(tag) = &self.event
-                        {
-                            ctx.emit(LogicEvent::Clicked(tag.clone(), button));
-                        }
+                        self.set_state(WidgetState::Cold, time);
+                        self.click(ctx, data, Some(MouseButton::Primary));
                     }
                 },
                 HardwareEvent::Key { key: keycode, pressed } =>
                 {
-                    if let Some(tag) = &self.event
+                    //focusable widgets only hear keys while focused
+                    if let Some(tag) = &self.event && (self.focus.is_none() || self.focused)
                     {
                         ctx.emit(LogicEvent::Pressed(tag.clone(), keycode, pressed));
                     }
@@ -91,41 +157,24 @@ impl<'a, T, E: Clone, W: Widget<T, E>> Widget<T, E> for Response<'a, T, E, W>
             }
             if update
             {
-                ctx.request.paint();
+                ctx.request.animate();
             }
         }
     }
 
-    impl_layout_inquire_child!(T);
-
-    #[inline]
-    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
-    {
-        self.size = self.child.layout_compute(ctx, data, size);
-        self.size
-    }
-
-    #[inline]
-    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
-    {
-        let old_state = ctx.state;
-        ctx.state = self.state;
-        self.child.paint(ctx, data);
-        ctx.state = old_state;
-    }
-}
-
-impl<'a, T, E: Clone, W: Widget<T, E>> Response<'a, T, E, W>
-{
     pub fn new(widget: W) -> Self
     {
         Self
         {
             child: widget,
             size: Vec2::zero(),
-            state: WidgetState::Cold,
+            state: Persistent::new(WidgetState::Cold),
             action: None,
             event: None,
+            focus: None,
+            focused: false,
+            state_from: WidgetState::Cold,
+            transition: f32::NEG_INFINITY,
         }
     }
 
@@ -141,6 +190,71 @@ impl<'a, T, E: Clone, W: Widget<T, E>> Response<'a, T, E, W>
         self
     }
 
+    pub fn persist(mut self, state: Persistent<WidgetState>) -> Self
+    {
+        self.state = state;
+        self
+    }
+
+    pub fn focusable(mut self, id: WidgetId) -> Self
+    {
+        self.focus = Some(id);
+        self
+    }
+
+    fn progress(&self, time: f32) -> f32
+    {
+        ((time - self.transition) / TRANSITION_TIME).clamp(0.0, 1.0)
+    }
+
+    //going back mid transition reverses it, any other change mid transition keeps it running towards the new state
+    fn set_state(&mut self, state: WidgetState, time: f32)
+    {
+        let old = self.state.get();
+        if old == state { return; }
+        let progress = self.progress(time);
+        if progress >= 1.0
+        {
+            self.state_from = old;
+            self.transition = time;
+        } else if state == self.state_from
+        {
+            self.state_from = old;
+            self.transition = time - (1.0 - progress) * TRANSITION_TIME;
+        }
+        self.state.set(state);
+    }
+
+    fn click(&mut self, ctx: &mut EventCtx<E>, data: &mut T, button: Option<MouseButton>)
+    {
+        let mut synthetic_event = WidgetEvent::Synthetic(SyntheticEvent::Clicked(button));
+        std::mem::swap(&mut ctx.event, &mut synthetic_event);
+        self.child.event(ctx, data);
+        std::mem::swap(&mut ctx.event, &mut synthetic_event);
+        if let Some(button) = button
+        {
+            if let Some(action) = &mut self.action { action(ctx.request, data); }
+            if let Some(tag) = &self.event
+            {
+                ctx.emit(LogicEvent::Clicked(tag.clone(), button));
+            }
+        }
+    }
+
+    fn sync_focus(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        let focused = self.focus.is_some() && ctx.focused() == self.focus;
+        if focused != self.focused
+        {
+            self.focused = focused;
+            let mut synthetic_event = WidgetEvent::Synthetic(if focused { SyntheticEvent::FocusGained } else { SyntheticEvent::FocusLost });
+            std::mem::swap(&mut ctx.event, &mut synthetic_event);
+            self.child.event(ctx, data);
+            std::mem::swap(&mut ctx.event, &mut synthetic_event);
+            ctx.request.paint();
+        }
+    }
+
     pub(crate) fn map_child<W2: Widget<T, E>>(self, f: impl FnOnce(W) -> W2) -> Response<'a, T, E, W2>
     {
         Response
@@ -150,6 +264,275 @@ impl<'a, T, E: Clone, W: Widget<T, E>> Response<'a, T, E, W>
             state: self.state,
             action: self.action,
             event: self.event,
+            focus: self.focus,
+            focused: self.focused,
+            state_from: self.state_from,
+            transition: self.transition,
+        }
+    }
+}
+
+pub struct Shortcuts<T, E: Clone, W: Widget<T, E>>
+{
+    child: W,
+    shortcuts: Vec<(KeyChord, E)>,
+    _phantom: PhantomData<T>,
+    //paint cache
+    scoped: bool,
+}
+
+impl<T, E: Clone, W: Widget<T, E>> Widget<T, E> for Shortcuts<T, E, W>
+{
+    #[inline]
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        //inner scopes get the first chance
+        self.child.event(ctx, data);
+        //the scope listens while it contains the focus or nothing is focused
+        if !self.scoped && ctx.focused().is_some() { return; }
+        let modifiers = ctx.modifiers;
+        if let WidgetEvent::Hardware(event) = &mut ctx.event && !event.used
+            && let HardwareEvent::Key { key, pressed: true } = event.event
+            && let Some((_, tag)) = self.shortcuts.iter().find(|(chord, _)| chord.matches(key, modifiers))
+        {
+            event.used = true;
+            ctx.emit(LogicEvent::Shortcut(tag.clone()));
+        }
+    }
+
+    impl_layout_inquire_child!(T);
+    impl_layout_compute_child!(T);
+
+    #[inline]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        let mark = ctx.focus.mark();
+        self.child.paint(ctx, data);
+        self.scoped = ctx.focus.current_since(mark);
+    }
+}
+
+impl<T, E: Clone, W: Widget<T, E>> Shortcuts<T, E, W>
+{
+    pub fn new(widget: W) -> Self
+    {
+        Self { child: widget, shortcuts: Vec::new(), _phantom: PhantomData, scoped: false }
+    }
+
+    pub fn shortcut(mut self, chord: KeyChord, tag: E) -> Self
+    {
+        self.shortcuts.push((chord, tag));
+        self
+    }
+}
+
+pub struct DragSource<'a, T, E, W: Widget<T, E>>
+{
+    child: W,
+    payload: Box<dyn FnMut(&T) -> Box<dyn Any> + 'a>,
+    //where the primary button went down, relative to the source
+    press: Option<Vec2>,
+    dragging: bool,
+    //absolute pointer positions at the press and now
+    start: Vec2,
+    pointer: Vec2,
+    //the layer pass saw the release, the drag ends after the hardware pass
+    released: bool,
+    _phantom: PhantomData<E>,
+    //layout cache
+    size: Vec2,
+}
+
+impl<'a, T, E, W: Widget<T, E>> Widget<T, E> for DragSource<'a, T, E, W>
+{
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        //the layer pass is never hidden or turned into PointerGone by a scroll view around the source
+        if let WidgetEvent::Layer(event) = &ctx.event
+        {
+            if let Some(pos) = event.event.pos() { self.pointer = pos; }
+            match event.event
+            {
+                HardwareEvent::PointerMoved { .. } if self.dragging => ctx.request.paint(),
+                HardwareEvent::PointerClicked { button: MouseButton::Primary, pressed: false, .. } | HardwareEvent::PointerGone if self.press.is_some() => self.released = true,
+                _ => {},
+            }
+        }
+        //while dragging the child does not see the pointer, so releasing does not click it
+        let hidden = match &mut ctx.event
+        {
+            WidgetEvent::Hardware(event) if self.dragging && matches!(event.event, HardwareEvent::PointerMoved { .. } | HardwareEvent::PointerClicked { .. }) => Some(std::mem::replace(&mut event.event, HardwareEvent::PointerGone)),
+            _ => None,
+        };
+        self.child.event(ctx, data);
+        let mut let_go = false;
+        if let WidgetEvent::Hardware(event) = &mut ctx.event
+        {
+            if let Some(original) = hidden { event.event = original; }
+            match event.event
+            {
+                HardwareEvent::PointerClicked { pos, button: MouseButton::Primary, pressed: true } => if Rect::new_origin(self.size).contains_linf(pos)
+                {
+                    self.press = Some(pos);
+                    self.start = self.pointer;
+                },
+                //a PointerGone here may only mean the pointer left a scroll view around the source, so it does not end the drag
+                HardwareEvent::PointerMoved { .. } | HardwareEvent::PointerGone => if self.press.is_some() && !self.released
+                {
+                    let moved = self.pointer - self.start;
+                    if !self.dragging && (moved.0 * moved.0 + moved.1 * moved.1).sqrt() > DRAG_THRESHOLD
+                    {
+                        self.dragging = true;
+                        ctx.pointer.drag = Some((self.payload)(data));
+                        ctx.request.paint();
+                        let_go = true;
+                    }
+                    if self.dragging && event.event != HardwareEvent::PointerGone { event.used = true; }
+                },
+                _ => {},
+            }
+            if self.released
+            {
+                self.released = false;
+                self.press = None;
+                if self.dragging
+                {
+                    self.dragging = false;
+                    ctx.request.paint();
+                }
+            }
+        }
+        if self.dragging && ctx.pointer.cursor == CursorIcon::Default { ctx.set_cursor(CursorIcon::Grabbing); }
+        //the child lets go of the pointer once the drag starts
+        if let_go && let WidgetEvent::Hardware(event) = &mut ctx.event
+        {
+            let moved = std::mem::replace(&mut event.event, HardwareEvent::PointerGone);
+            self.child.event(ctx, data);
+            if let WidgetEvent::Hardware(event) = &mut ctx.event { event.event = moved; }
+        }
+    }
+
+    impl_layout_inquire_child!(T);
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
+    {
+        self.size = self.child.layout_compute(ctx, data, size);
+        self.size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        self.child.paint(ctx, data);
+        //the preview follows the pointer above everything else, as a ghost so the child is not registered twice
+        if let Some(press) = self.press && self.dragging
+        {
+            ctx.painter.push_top_layer(self.pointer - press);
+            let ghost = ctx.ghost;
+            ctx.ghost = true;
+            self.child.paint(ctx, data);
+            ctx.ghost = ghost;
+            ctx.painter.pop_layer();
+        }
+    }
+}
+
+impl<'a, T, E, W: Widget<T, E>> DragSource<'a, T, E, W>
+{
+    pub fn new<P: Any>(widget: W, mut payload: impl FnMut(&T) -> P + 'a) -> Self
+    {
+        Self
+        {
+            child: widget,
+            payload: Box::new(move |data| Box::new(payload(data)) as Box<dyn Any>),
+            press: None,
+            dragging: false,
+            start: Vec2::zero(),
+            pointer: Vec2::zero(),
+            released: false,
+            _phantom: PhantomData,
+            size: Vec2::zero(),
         }
     }
 }
+
+pub struct DropTarget<'a, T, E, W: Widget<T, E>, P: Any>
+{
+    child: W,
+    accept: Box<dyn FnMut(&T, &P) -> bool + 'a>,
+    drop: Box<dyn FnMut(&mut T, P) + 'a>,
+    //an acceptable payload hovers above
+    hover: bool,
+    _phantom: PhantomData<E>,
+    //layout cache
+    size: Vec2,
+}
+
+impl<'a, T, E, W: Widget<T, E>, P: Any> Widget<T, E> for DropTarget<'a, T, E, W, P>
+{
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        self.child.event(ctx, data);
+        let WidgetEvent::Hardware(event) = &mut ctx.event else { return; };
+        let inside = event.event.pos().is_some_and(|pos| Rect::new_origin(self.size).contains_linf(pos));
+        let accepted = inside && ctx.pointer.drag.as_ref().and_then(|payload| payload.downcast_ref::<P>()).is_some_and(|payload| (self.accept)(data, payload));
+        match event.event
+        {
+            HardwareEvent::PointerMoved { .. } | HardwareEvent::PointerGone =>
+            {
+                if inside && ctx.pointer.drag.is_some() { ctx.pointer.cursor = if accepted { CursorIcon::Grabbing } else { CursorIcon::NotAllowed }; }
+                if accepted != self.hover
+                {
+                    self.hover = accepted;
+                    ctx.request.paint();
+                }
+            },
+            HardwareEvent::PointerClicked { button: MouseButton::Primary, pressed: false, .. } =>
+            {
+                if accepted && !event.used && let Some(payload) = ctx.pointer.drag.take() && let Ok(payload) = payload.downcast::<P>()
+                {
+                    (self.drop)(data, *payload);
+                    event.used = true;
+                    ctx.request.widget();
+                }
+                if self.hover
+                {
+                    self.hover = false;
+                    ctx.request.paint();
+                }
+            },
+            _ => {},
+        }
+    }
+
+    impl_layout_inquire_child!(T);
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
+    {
+        self.size = self.child.layout_compute(ctx, data, size);
+        self.size
+    }
+
+    #[inline]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        self.child.paint(ctx, data);
+        if self.hover { ctx.draw_outline(Rect::new_origin(self.size), DROP_WIDTH, ctx.style.data.hot); }
+    }
+}
+
+impl<'a, T, E, W: Widget<T, E>, P: Any> DropTarget<'a, T, E, W, P>
+{
+    pub fn new(widget: W, drop: impl FnMut(&mut T, P) + 'a) -> Self
+    {
+        Self { child: widget, accept: Box::new(|_, _| true), drop: Box::new(drop), hover: false, _phantom: PhantomData, size: Vec2::zero() }
+    }
+
+    //payloads of the right type can still be rejected, e.g. a full inventory slot
+    pub fn accept(mut self, accept: impl FnMut(&T, &P) -> bool + 'a) -> Self
+    {
+        self.accept = Box::new(accept);
+        self
+    }
+}
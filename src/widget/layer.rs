@@ -0,0 +1,240 @@
+use super::*;
+use crate::timer::TimerId;
+
+const TOOLTIP_DELAY: f32 = 0.5;
+const TOOLTIP_OFFSET: f32 = 0.5;
+
+pub enum Anchor
+{
+    Below,
+    Above,
+    Right,
+    Left,
+    Center,
+    Point(Vec2),
+}
+
+pub struct Overlay<T, E, W: Widget<T, E>, C: Widget<T, E>>
+{
+    child: W,
+    content: C,
+    anchor: Anchor,
+    open: Persistent<bool>,
+    top: bool,
+    _phantom: PhantomData<(T, E)>,
+    //layout cache
+    size: Vec2,
+    content_size: Vec2,
+    misfit: Option<usize>,
+    //paint cache
+    pos: Vec2,
+}
+
+impl<T, E, W: Widget<T, E>, C: Widget<T, E>> Widget<T, E> for Overlay<T, E, W, C>
+{
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        match &ctx.event
+        {
+            WidgetEvent::Layer(_) =>
+            {
+                if self.open.get()
+                {
+                    //deeper layers first, then the content itself in its local space
+                    self.content.event(ctx, data);
+                    ctx.event = std::mem::replace(&mut ctx.event, WidgetEvent::NewData).into_hardware();
+                    ctx.event.offset(-self.pos);
+                    self.content.event(ctx, data);
+                    if let WidgetEvent::Hardware(event) = &mut ctx.event
+                    && let Some(pos) = event.event.pos()
+                    && Rect::new_origin(self.content_size).contains_linf(pos)
+                    {
+                        event.used = true;
+                    }
+                    ctx.event.offset(self.pos);
+                    ctx.event = std::mem::replace(&mut ctx.event, WidgetEvent::NewData).into_layer();
+                }
+                self.child.event(ctx, data);
+            },
+            WidgetEvent::Hardware(_) | WidgetEvent::Synthetic(_) => self.child.event(ctx, data),
+            _ =>
+            {
+                self.child.event(ctx, data);
+                self.content.event(ctx, data);
+            },
+        }
+    }
+
+    impl_layout_inquire_child!(T);
+
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
+    {
+        self.size = self.child.layout_compute(ctx, data, size);
+        //the content gets its min size, but never more than the screen it is kept on
+        let min_size = self.content.layout_inquire(&mut ctx.inquire(), data);
+        let screen = ctx.screen_size();
+        self.misfit = ctx.does_not_fit("Overlay", min_size, screen);
+        ctx.push_path("Overlay", None);
+        self.content_size = self.content.layout_compute(ctx, data, Vec2(min_size.0.min(screen.0), min_size.1.min(screen.1)));
+        ctx.pop_path();
+        self.size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        self.child.paint(ctx, data);
+        if self.open.get()
+        {
+            let (size, content_size) = (self.size, self.content_size);
+            let anchor = match self.anchor
+            {
+                Anchor::Below => Vec2(0.0, size.1),
+                Anchor::Above => Vec2(0.0, -content_size.1),
+                Anchor::Right => Vec2(size.0, 0.0),
+                Anchor::Left => Vec2(-content_size.0, 0.0),
+                Anchor::Center => (size - content_size) / 2.0,
+                Anchor::Point(pos) => pos,
+            };
+            //keep the content on screen
+            let max = ctx.screen_size() - content_size;
+            let pos = ctx.origin() + anchor;
+            self.pos = Vec2(pos.0.min(max.0).max(0.0), pos.1.min(max.1).max(0.0));
+            if self.top { ctx.painter.push_top_layer(self.pos); } else { ctx.push_layer(self.pos); }
+            if let Some(misfit) = self.misfit { ctx.misfit(misfit); }
+            self.content.paint(ctx, data);
+            ctx.pop_layer();
+        }
+    }
+}
+
+impl<T, E, W: Widget<T, E>, C: Widget<T, E>> Overlay<T, E, W, C>
+{
+    pub fn new(widget: W, content: C) -> Self
+    {
+        Self
+        {
+            child: widget,
+            content,
+            anchor: Anchor::Below,
+            open: Persistent::new(false),
+            top: false,
+            _phantom: PhantomData,
+            size: Vec2::zero(),
+            content_size: Vec2::zero(),
+            misfit: None,
+            pos: Vec2::zero(),
+        }
+    }
+
+    pub fn anchor(mut self, anchor: Anchor) -> Self
+    {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn below(self) -> Self { self.anchor(Anchor::Below) }
+    pub fn above(self) -> Self { self.anchor(Anchor::Above) }
+    pub fn right(self) -> Self { self.anchor(Anchor::Right) }
+    pub fn left(self) -> Self { self.anchor(Anchor::Left) }
+    pub fn center(self) -> Self { self.anchor(Anchor::Center) }
+
+    //above every other layer instead of just the one it is opened from
+    pub fn top(mut self) -> Self
+    {
+        self.top = true;
+        self
+    }
+
+    //closed by default
+    pub fn open(self, open: bool) -> Self
+    {
+        self.open.set(open);
+        self
+    }
+
+    //opened and closed from outside, e.g. by a button setting it and requesting a paint
+    pub fn persist(mut self, open: Persistent<bool>) -> Self
+    {
+        self.open = open;
+        self
+    }
+
+    pub fn is_open(&self) -> bool
+    {
+        self.open.get()
+    }
+
+    pub(crate) fn set_open(&mut self, open: bool, anchor: Option<Anchor>)
+    {
+        self.open.set(open);
+        if let Some(anchor) = anchor { self.anchor = anchor; }
+    }
+}
+
+pub struct Tooltip<T, E, W: Widget<T, E>, C: Widget<T, E>>
+{
+    overlay: Overlay<T, E, W, C>,
+    delay: f32,
+    timer: Option<TimerId>,
+    //last pointer position on the widget
+    pos: Vec2,
+}
+
+impl<T, E, W: Widget<T, E>, C: Widget<T, E>> Widget<T, E> for Tooltip<T, E, W, C>
+{
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        if let WidgetEvent::Timer(id) = ctx.event && self.timer == Some(id)
+        {
+            self.timer = None;
+            self.overlay.set_open(true, Some(Anchor::Point(self.pos + Vec2(TOOLTIP_OFFSET, TOOLTIP_OFFSET))));
+            ctx.request.paint();
+            return;
+        }
+        self.overlay.event(ctx, data);
+        let size = self.overlay.size;
+        let hover = match &ctx.event
+        {
+            WidgetEvent::Hardware(event) => match event.event
+            {
+                HardwareEvent::PointerMoved { pos, .. } =>
+                {
+                    let hover = Rect::new_origin(size).contains_linf(pos);
+                    if hover { self.pos = pos; }
+                    hover
+                },
+                HardwareEvent::PointerClicked { .. } | HardwareEvent::PointerGone => false,
+                _ => return,
+            },
+            _ => return,
+        };
+        //every move restarts the wait, leaving or clicking hides it
+        if let Some(id) = self.timer.take() { ctx.cancel(id); }
+        if hover && !self.overlay.is_open() { self.timer = Some(ctx.schedule(self.delay)); }
+        if !hover && self.overlay.is_open()
+        {
+            self.overlay.set_open(false, None);
+            ctx.request.paint();
+        }
+    }
+
+    #[inline] fn layout_inquire(&mut self, ctx: &mut LayoutInquireCtx, data: &T) -> Vec2 { self.overlay.layout_inquire(ctx, data) }
+    #[inline] fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2 { self.overlay.layout_compute(ctx, data, size) }
+    #[inline] fn paint(&mut self, ctx: &mut PaintCtx, data: &T) { self.overlay.paint(ctx, data); }
+}
+
+impl<T, E, W: Widget<T, E>, C: Widget<T, E>> Tooltip<T, E, W, C>
+{
+    pub fn new(widget: W, content: C) -> Self
+    {
+        let overlay = Overlay::new(widget, content).top();
+        Self { overlay, delay: TOOLTIP_DELAY, timer: None, pos: Vec2::zero() }
+    }
+
+    //seconds the pointer has to rest on the widget
+    pub fn delay(mut self, delay: f32) -> Self
+    {
+        self.delay = delay;
+        self
+    }
+}
@@ -18,6 +18,9 @@ pub struct Flex<'a, const ROW: bool, T, E>
     //layout cache
     child_size_or_offset: Vec<f32>,
     total_primary_size: f32,
+    min_size: Vec2,
+    size: Vec2,
+    misfit: Option<usize>,
 }
 
 impl<'a, const ROW: bool, T, E> Widget<T, E> for Flex<'a, ROW, T, E>
@@ -47,6 +50,7 @@ impl<'a, const ROW: bool, T, E> Widget<T, E> for Flex<'a, ROW, T, E>
         }
         *primary += self.padding * (self.childs.len().max(1) - 1) as f32;
         self.total_primary_size = *primary;
+        self.min_size = size;
         size
     }
 
@@ -54,9 +58,10 @@ impl<'a, const ROW: bool, T, E> Widget<T, E> for Flex<'a, ROW, T, E>
     {
         let (primary, secondary) = if ROW { (size.0, size.1) } else { (size.1, size.0) };
         let mut delta = primary - self.total_primary_size;
+        self.misfit = None;
         if delta < -ERR
         {
-            ctx.does_not_fit();
+            self.misfit = ctx.does_not_fit(if ROW { "Row" } else { "Column" }, self.min_size, size);
             delta = 0.0;
         }
         let child_count = self.childs.len() as f32;
@@ -82,17 +87,22 @@ impl<'a, const ROW: bool, T, E> Widget<T, E> for Flex<'a, ROW, T, E>
             let child_primary = self.child_size_or_offset[i] + growth;
             let child_secondary = secondary;
             let child_size = if ROW { Vec2(child_primary, child_secondary) } else { Vec2(child_secondary, child_primary) };
+            ctx.push_path(if ROW { "Row" } else { "Column" }, Some(i));
             child.layout_compute(ctx, data, child_size);
+            ctx.pop_path();
             self.child_size_or_offset[i] = offset;
             offset += child_primary + pad_mid;
         }
         if self.childs.len() > 0 { offset -= pad_mid; }
         offset += pad_back;
-        if ROW { Vec2(offset, secondary) } else { Vec2(secondary, offset) }
+        self.size = if ROW { Vec2(offset, secondary) } else { Vec2(secondary, offset) };
+        self.size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
     {
+        ctx.record(if ROW { "Row" } else { "Column" }, None, self.size, false);
+        if let Some(misfit) = self.misfit { ctx.misfit(misfit); }
         for (i, child) in self.childs.iter_mut().enumerate()
         {
             let offset = if ROW { Vec2(self.child_size_or_offset[i], 0.0) } else { Vec2(0.0, self.child_size_or_offset[i]) };
@@ -114,6 +124,9 @@ impl<'a, const ROW: bool, T, E> Flex<'a, ROW, T, E>
             layout: FlexLayout::Front,
             child_size_or_offset: Vec::new(),
             total_primary_size: 0.0,
+            min_size: Vec2::zero(),
+            size: Vec2::zero(),
+            misfit: None,
         }
     }
 
@@ -1,9 +1,13 @@
 mod flex;
+mod scroll;
 pub use flex::*;
+pub use scroll::*;
 
 use super::*;
 
 const ERR: f32 = 1e-3;
+const HANDLE_WIDTH: f32 = 0.3;
+const MIN_RESIZE: f32 = 1.0;
 
 pub struct Empty;
 
@@ -70,6 +74,112 @@ impl<T, E, W: Widget<T, E>> Fix<T, E, W>
     }
 }
 
+//the right and bottom edge can be dragged to change the size, e.g. of a side panel
+pub struct Resize<T, E, W: Widget<T, E>>
+{
+    child: W,
+    width: Option<Persistent<f32>>,
+    height: Option<Persistent<f32>>,
+    //edge being dragged, true for the bottom one
+    dragged: Option<bool>,
+    _phantom: PhantomData<(T, E)>,
+    //layout cache
+    size: Vec2
+}
+
+impl<T, E, W: Widget<T, E>> Widget<T, E> for Resize<T, E, W>
+{
+    #[inline]
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        //the edges come before the child, they lie on top of it
+        if let WidgetEvent::Hardware(event) = &mut ctx.event
+        {
+            match event.event
+            {
+                HardwareEvent::PointerClicked { pos, button: MouseButton::Primary, pressed: true } if !event.used =>
+                {
+                    self.dragged = self.edge(pos);
+                    if self.dragged.is_some() { event.used = true; }
+                },
+                HardwareEvent::PointerClicked { button: MouseButton::Primary, pressed: false, .. } => self.dragged = None,
+                HardwareEvent::PointerMoved { pos, .. } => if let Some(vertical) = self.dragged
+                {
+                    let (length, along) = if vertical { (&self.height, pos.1) } else { (&self.width, pos.0) };
+                    if let Some(length) = length { length.set(along.max(MIN_RESIZE)); }
+                    ctx.request.layout();
+                    event.used = true;
+                },
+                _ => {},
+            }
+        }
+        self.child.event(ctx, data);
+        let pointer = match &ctx.event { WidgetEvent::Hardware(event) => event.event.pos(), _ => None };
+        if let Some(pos) = pointer && let Some(vertical) = self.dragged.or_else(|| self.edge(pos))
+        {
+            ctx.set_cursor(if vertical { CursorIcon::ResizeVertical } else { CursorIcon::ResizeHorizontal });
+        }
+    }
+
+    #[inline]
+    fn layout_inquire(&mut self, ctx: &mut LayoutInquireCtx, data: &T) -> Vec2
+    {
+        let mut size = self.child.layout_inquire(ctx, data);
+        if let Some(width) = &self.width { size.0 = width.get(); }
+        if let Some(height) = &self.height { size.1 = height.get(); }
+        size
+    }
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, mut size: Vec2) -> Vec2
+    {
+        if let Some(width) = &self.width { size.0 = width.get(); }
+        if let Some(height) = &self.height { size.1 = height.get(); }
+        size = self.child.layout_compute(ctx, data, size);
+        if let Some(width) = &self.width { size.0 = width.get(); }
+        if let Some(height) = &self.height { size.1 = height.get(); }
+        self.size = size;
+        size
+    }
+
+    #[inline]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        ctx.record("Resize", None, self.size, false);
+        self.child.paint(ctx, data);
+    }
+}
+
+impl<T, E, W: Widget<T, E>> Resize<T, E, W>
+{
+    pub fn new(widget: W) -> Self
+    {
+        Self { child: widget, width: None, height: None, dragged: None, _phantom: PhantomData, size: Vec2::zero() }
+    }
+
+    //makes the right edge draggable, the width lives on across rebuilds
+    pub fn width(mut self, width: Persistent<f32>) -> Self
+    {
+        self.width = Some(width);
+        self
+    }
+
+    //makes the bottom edge draggable, the height lives on across rebuilds
+    pub fn height(mut self, height: Persistent<f32>) -> Self
+    {
+        self.height = Some(height);
+        self
+    }
+
+    fn edge(&self, pos: Vec2) -> Option<bool>
+    {
+        if !Rect::new_origin(self.size).contains_linf(pos) { return None; }
+        if self.height.is_some() && pos.1 >= self.size.1 - HANDLE_WIDTH { Some(true) }
+        else if self.width.is_some() && pos.0 >= self.size.0 - HANDLE_WIDTH { Some(false) }
+        else { None }
+    }
+}
+
 pub enum AlignLayout
 {
     Front,
@@ -173,7 +283,11 @@ pub struct Padding<T, E, W: Widget<T, E>>
 {
     child: W,
     padding: Rect,
-    _phantom: PhantomData<(T, E)>
+    _phantom: PhantomData<(T, E)>,
+    //layout cache
+    min_size: Vec2,
+    size: Vec2,
+    misfit: Option<usize>
 }
 
 impl<T, E, W: Widget<T, E>> Widget<T, E> for Padding<T, E, W>
@@ -189,21 +303,29 @@ impl<T, E, W: Widget<T, E>> Widget<T, E> for Padding<T, E, W>
     #[inline]
     fn layout_inquire(&mut self, ctx: &mut LayoutInquireCtx, data: &T) -> Vec2
     {
-        self.child.layout_inquire(ctx, data) + self.padding.min + self.padding.max
+        self.min_size = self.child.layout_inquire(ctx, data) + self.padding.min + self.padding.max;
+        self.min_size
     }
 
     #[inline]
     fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
     {
+        let allotted = size;
         let size = size - self.padding.min - self.padding.max;
-        if size.0 <= 0.0 || size.1 <= 0.0 { ctx.does_not_fit(); }
+        self.misfit = None;
+        if size.0 <= 0.0 || size.1 <= 0.0 { self.misfit = ctx.does_not_fit("Padding", self.min_size, allotted); }
+        ctx.push_path("Padding", None);
         let size = self.child.layout_compute(ctx, data, size);
-        size + self.padding.min + self.padding.max
+        ctx.pop_path();
+        self.size = size + self.padding.min + self.padding.max;
+        self.size
     }
 
     #[inline]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
     {
+        ctx.record("Padding", None, self.size, false);
+        if let Some(misfit) = self.misfit { ctx.misfit(misfit); }
         ctx.add_offset(self.padding.min);
         self.child.paint(ctx, data);
         ctx.add_offset(-self.padding.min);
@@ -214,7 +336,7 @@ impl<T, E, W: Widget<T, E>> Padding<T, E, W>
 {
     pub fn new(widget: W) -> Self
     {
-        Self { child: widget, padding: Rect::new_origin(Vec2::zero()), _phantom: PhantomData }
+        Self { child: widget, padding: Rect::new_origin(Vec2::zero()), _phantom: PhantomData, min_size: Vec2::zero(), size: Vec2::zero(), misfit: None }
     }
 
     pub fn left(mut self, padding: f32) -> Self
@@ -0,0 +1,312 @@
+use super::*;
+
+const SCROLL_STEP: f32 = 1.0;
+const BAR_WIDTH: f32 = 0.5;
+const MIN_THUMB: f32 = 1.0;
+const PAN_THRESHOLD: f32 = 0.5;
+
+#[inline] fn axis(v: Vec2, vertical: bool) -> f32 { if vertical { v.1 } else { v.0 } }
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pan
+{
+    //below the threshold, the child still has the pointer
+    Waiting,
+    Panning,
+    //the finger went up, the emulated release and pointer gone that follow still belong to the pan
+    Lifted,
+    //a second finger took the emulated pointer away, the first one keeps panning
+    Detached,
+}
+
+pub struct Scroll<T, E, W: Widget<T, E>>
+{
+    child: W,
+    horizontal: bool,
+    vertical: bool,
+    bar_width: f32,
+    offset: Persistent<Vec2>,
+    //(vertical, pointer position relative to the thumb)
+    drag: Option<(bool, f32)>,
+    //(finger, start, last position, state)
+    pan: Option<(u64, Vec2, Vec2, Pan)>,
+    _phantom: PhantomData<(T, E)>,
+    //layout cache
+    child_min: Vec2,
+    size: Vec2,
+    view: Vec2,
+    child_size: Vec2,
+}
+
+impl<T, E, W: Widget<T, E>> Widget<T, E> for Scroll<T, E, W>
+{
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        let view = self.view;
+        let panning = self.pan.is_some_and(|(_, _, _, pan)| pan != Pan::Waiting);
+        //the child only sees pointer positions inside the visible part and no pointer at all while a finger pans
+        let outside = match &mut ctx.event
+        {
+            WidgetEvent::Hardware(event) => match event.event
+            {
+                HardwareEvent::PointerMoved { .. } | HardwareEvent::PointerClicked { .. } if panning => Some(std::mem::replace(&mut event.event, HardwareEvent::PointerGone)),
+                _ => match event.event.pos()
+                {
+                    Some(pos) if !Rect::new_origin(view).contains_linf(pos) => Some(std::mem::replace(&mut event.event, HardwareEvent::PointerGone)),
+                    _ => None,
+                },
+            },
+            _ => None,
+        };
+        let offset = self.offset.get();
+        ctx.event.offset(offset);
+        self.child.event(ctx, data);
+        ctx.event.offset(-offset);
+        let mut let_go = false;
+        if let WidgetEvent::Hardware(event) = &mut ctx.event
+        {
+            if let Some(original) = outside { event.event = original; }
+            if event.used { return; }
+            match event.event
+            {
+                HardwareEvent::Scroll { pos, delta } => if Rect::new_origin(self.size).contains_linf(pos)
+                {
+                    let delta = match (self.horizontal, self.vertical)
+                    {
+                        (true, false) => Vec2(delta.0 + delta.1, 0.0),
+                        (false, true) => Vec2(0.0, delta.1),
+                        _ => delta,
+                    };
+                    if self.scroll_to(offset - delta * SCROLL_STEP) { ctx.request.paint(); }
+                    event.used = true;
+                },
+                HardwareEvent::PointerClicked { pos, button: MouseButton::Primary, pressed: true } =>
+                {
+                    for vertical in [false, true]
+                    {
+                        let Some(track) = self.track(vertical) else { continue; };
+                        if !track.contains_linf(pos) { continue; }
+                        let (start, length) = self.thumb(vertical);
+                        let along = axis(pos - track.min, vertical);
+                        //clicking the track next to the thumb jumps there
+                        if along < start || along > start + length
+                        {
+                            self.drag_thumb(vertical, along - length / 2.0);
+                            ctx.request.paint();
+                        }
+                        let (start, _) = self.thumb(vertical);
+                        self.drag = Some((vertical, along - start));
+                        event.used = true;
+                        break;
+                    }
+                },
+                HardwareEvent::PointerClicked { button: MouseButton::Primary, pressed: false, .. } => if self.drag.take().is_some()
+                {
+                    ctx.request.paint();
+                    event.used = true;
+                },
+                HardwareEvent::PointerGone =>
+                {
+                    if self.drag.take().is_some() { ctx.request.paint(); }
+                    self.pan = match self.pan
+                    {
+                        Some((finger, start, last, Pan::Panning)) => Some((finger, start, last, Pan::Detached)),
+                        _ => None,
+                    };
+                },
+                HardwareEvent::PointerMoved { pos, .. } => if let Some((vertical, grab)) = self.drag
+                {
+                    if let Some(track) = self.track(vertical) && self.drag_thumb(vertical, axis(pos - track.min, vertical) - grab) { ctx.request.paint(); }
+                },
+                //further fingers do not take over the pan
+                HardwareEvent::Touch { id, phase: TouchPhase::Start, pos } => if self.pan.is_none() && Rect::new_origin(view).contains_linf(pos) { self.pan = Some((id, pos, pos, Pan::Waiting)); },
+                HardwareEvent::Touch { id, phase: TouchPhase::Move, pos } => if let Some((finger, start, last, mut pan)) = self.pan && finger == id
+                {
+                    let moved = pos - start;
+                    if pan == Pan::Waiting && (moved.0 * moved.0 + moved.1 * moved.1).sqrt() > PAN_THRESHOLD
+                    {
+                        pan = Pan::Panning;
+                        let_go = true;
+                    }
+                    if pan != Pan::Waiting
+                    {
+                        if self.scroll_to(offset - (pos - last)) { ctx.request.paint(); }
+                        event.used = true;
+                    }
+                    self.pan = Some((finger, start, pos, pan));
+                },
+                HardwareEvent::Touch { id, phase: TouchPhase::End | TouchPhase::Cancel, .. } => if let Some((finger, start, last, pan)) = self.pan && finger == id
+                {
+                    self.pan = if pan == Pan::Panning { Some((finger, start, last, Pan::Lifted)) } else { None };
+                },
+                _ => {},
+            }
+        }
+        //the child lets go of the pointer once panning starts
+        if let_go && let WidgetEvent::Hardware(event) = &mut ctx.event
+        {
+            let touch = std::mem::replace(&mut event.event, HardwareEvent::PointerGone);
+            self.child.event(ctx, data);
+            if let WidgetEvent::Hardware(event) = &mut ctx.event { event.event = touch; }
+        }
+    }
+
+    #[inline]
+    fn layout_inquire(&mut self, ctx: &mut LayoutInquireCtx, data: &T) -> Vec2
+    {
+        self.child_min = self.child.layout_inquire(ctx, data);
+        let bars = self.bars();
+        //along the scrolling axes the content does not need to fit
+        Vec2
+        (
+            if self.horizontal { bars.0 + MIN_THUMB } else { self.child_min.0 + bars.0 },
+            if self.vertical { bars.1 + MIN_THUMB } else { self.child_min.1 + bars.1 }
+        )
+    }
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
+    {
+        self.size = size;
+        self.view = (size - self.bars()).component_max(Vec2::zero());
+        let wish = Vec2
+        (
+            if self.horizontal { self.child_min.0.max(self.view.0) } else { self.view.0 },
+            if self.vertical { self.child_min.1.max(self.view.1) } else { self.view.1 }
+        );
+        ctx.push_path("Scroll", None);
+        self.child_size = self.child.layout_compute(ctx, data, wish);
+        ctx.pop_path();
+        self.scroll_to(self.offset.get());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        ctx.record("Scroll", None, self.size, true);
+        let offset = self.offset.get();
+        ctx.access_push(Role::Group, self.size);
+        ctx.push_clip(Rect::new_origin(self.view));
+        ctx.add_offset(-offset);
+        self.child.paint(ctx, data);
+        ctx.add_offset(offset);
+        ctx.pop_clip();
+        ctx.access_pop();
+        for vertical in [false, true]
+        {
+            let Some(track) = self.track(vertical) else { continue; };
+            ctx.painter.draw_rect(track, ctx.style.top);
+            if axis(self.child_size - self.view, vertical) <= ERR { continue; }
+            let (start, length) = self.thumb(vertical);
+            let thumb = if vertical
+            {
+                Rect { min: track.min + Vec2(0.0, start), max: Vec2(track.max.0, track.min.1 + start + length) }
+            } else
+            {
+                Rect { min: track.min + Vec2(start, 0.0), max: Vec2(track.min.0 + start + length, track.max.1) }
+            };
+            let color = if self.drag.is_some_and(|(drag, _)| drag == vertical) { ctx.style.data.hot } else { ctx.color(&ctx.style.data) };
+            ctx.painter.draw_rect(thumb, color);
+        }
+    }
+}
+
+impl<T, E, W: Widget<T, E>> Scroll<T, E, W>
+{
+    pub fn new(widget: W) -> Self
+    {
+        Self
+        {
+            child: widget,
+            horizontal: false,
+            vertical: true,
+            bar_width: BAR_WIDTH,
+            offset: Persistent::new(Vec2::zero()),
+            drag: None,
+            pan: None,
+            _phantom: PhantomData,
+            child_min: Vec2::zero(),
+            size: Vec2::zero(),
+            view: Vec2::zero(),
+            child_size: Vec2::zero(),
+        }
+    }
+
+    pub fn horizontal(mut self, horizontal: bool) -> Self
+    {
+        self.horizontal = horizontal;
+        self
+    }
+
+    pub fn vertical(mut self, vertical: bool) -> Self
+    {
+        self.vertical = vertical;
+        self
+    }
+
+    pub fn both(self) -> Self { self.horizontal(true).vertical(true) }
+
+    pub fn bar_width(mut self, width: f32) -> Self
+    {
+        self.bar_width = width;
+        self
+    }
+
+    pub fn persist(mut self, offset: Persistent<Vec2>) -> Self
+    {
+        self.offset = offset;
+        self
+    }
+
+    //space taken by the scrollbars, the vertical bar sits right and the horizontal one below
+    fn bars(&self) -> Vec2
+    {
+        Vec2(if self.vertical { self.bar_width } else { 0.0 }, if self.horizontal { self.bar_width } else { 0.0 })
+    }
+
+    fn track(&self, vertical: bool) -> Option<Rect>
+    {
+        match vertical
+        {
+            true if self.vertical => Some(Rect { min: Vec2(self.view.0, 0.0), max: Vec2(self.size.0, self.view.1) }),
+            false if self.horizontal => Some(Rect { min: Vec2(0.0, self.view.1), max: Vec2(self.view.0, self.size.1) }),
+            _ => None,
+        }
+    }
+
+    //start and length of the thumb along its track
+    fn thumb(&self, vertical: bool) -> (f32, f32)
+    {
+        let (view, content, offset) = (axis(self.view, vertical), axis(self.child_size, vertical), axis(self.offset.get(), vertical));
+        let length = (view * view / content.max(ERR)).max(MIN_THUMB.min(view)).min(view);
+        let range = content - view;
+        let start = if range > ERR { offset / range * (view - length) } else { 0.0 };
+        (start, length)
+    }
+
+    fn drag_thumb(&mut self, vertical: bool, start: f32) -> bool
+    {
+        let (_, length) = self.thumb(vertical);
+        let (view, content) = (axis(self.view, vertical), axis(self.child_size, vertical));
+        let free = view - length;
+        if free <= ERR { return false; }
+        let mut offset = self.offset.get();
+        let value = start / free * (content - view);
+        if vertical { offset.1 = value; } else { offset.0 = value; }
+        self.scroll_to(offset)
+    }
+
+    //clamps to the scrollable range, returns whether the offset changed
+    fn scroll_to(&mut self, offset: Vec2) -> bool
+    {
+        let max = (self.child_size - self.view).component_max(Vec2::zero());
+        let offset = Vec2
+        (
+            if self.horizontal { offset.0.max(0.0).min(max.0) } else { 0.0 },
+            if self.vertical { offset.1.max(0.0).min(max.1) } else { 0.0 }
+        );
+        let changed = offset != self.offset.get();
+        self.offset.set(offset);
+        changed
+    }
+}
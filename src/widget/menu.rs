@@ -0,0 +1,366 @@
+use super::*;
+
+const ROW_HEIGHT: f32 = 1.0;
+const PADDING: f32 = 0.3;
+const GAP: f32 = 1.0;
+const ARROW: &str = ">";
+
+enum EntryKind<E>
+{
+    Action(E),
+    Submenu(Menu<E>),
+}
+
+pub struct MenuEntry<E>
+{
+    label: String,
+    //only a hint, the chord itself has to be registered as a shortcut
+    shortcut: Option<KeyChord>,
+    enabled: bool,
+    kind: EntryKind<E>,
+}
+
+impl<E> MenuEntry<E>
+{
+    pub fn new(label: impl Into<String>, tag: E) -> Self
+    {
+        Self { label: label.into(), shortcut: None, enabled: true, kind: EntryKind::Action(tag) }
+    }
+
+    pub fn submenu(label: impl Into<String>, menu: Menu<E>) -> Self
+    {
+        Self { label: label.into(), shortcut: None, enabled: true, kind: EntryKind::Submenu(menu) }
+    }
+
+    pub fn shortcut(mut self, chord: KeyChord) -> Self
+    {
+        self.shortcut = Some(chord);
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self
+    {
+        self.enabled = enabled;
+        self
+    }
+}
+
+pub struct Menu<E>
+{
+    entries: Vec<MenuEntry<E>>,
+}
+
+impl<E> Menu<E>
+{
+    pub fn new() -> Self
+    {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn entry(mut self, entry: MenuEntry<E>) -> Self
+    {
+        self.entries.push(entry);
+        self
+    }
+
+    pub fn action(self, label: impl Into<String>, tag: E) -> Self { self.entry(MenuEntry::new(label, tag)) }
+    pub fn submenu(self, label: impl Into<String>, menu: Menu<E>) -> Self { self.entry(MenuEntry::submenu(label, menu)) }
+
+    fn first_enabled(&self) -> Option<usize>
+    {
+        self.entries.iter().position(|entry| entry.enabled)
+    }
+
+    //next enabled entry in the given direction, wrapping around
+    fn step(&self, from: Option<usize>, down: bool) -> Option<usize>
+    {
+        let len = self.entries.len();
+        if len == 0 { return None; }
+        let mut index = from.unwrap_or(if down { len - 1 } else { 0 });
+        for _ in 0..len
+        {
+            index = if down { (index + 1) % len } else { (index + len - 1) % len };
+            if self.entries[index].enabled { return Some(index); }
+        }
+        None
+    }
+}
+
+pub struct ContextMenu<T, E: Clone, W: Widget<T, E>>
+{
+    child: W,
+    menu: Menu<E>,
+    open: bool,
+    //absolute position of the root panel
+    pos: Vec2,
+    //hovered entry of every open panel, a hovered submenu opens the next panel
+    levels: Vec<Option<usize>>,
+    //a press went down inside the menu, so the release may choose
+    pressed: bool,
+    _phantom: PhantomData<T>,
+    //layout cache
+    size: Vec2,
+    //paint cache
+    origin: Vec2,
+    scoped: bool,
+    panels: Vec<Rect>,
+}
+
+impl<T, E: Clone, W: Widget<T, E>> Widget<T, E> for ContextMenu<T, E, W>
+{
+    fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut T)
+    {
+        if let WidgetEvent::Layer(_) = ctx.event && self.open { self.menu_event(ctx); }
+        //open before the child gets the chance to use the click
+        if let WidgetEvent::Hardware(event) = &mut ctx.event && !event.used
+        {
+            let opened = match event.event
+            {
+                HardwareEvent::PointerClicked { pos, button: MouseButton::Secondary, pressed: true } if Rect::new_origin(self.size).contains_linf(pos) => Some((pos, None)),
+                HardwareEvent::GamepadButton { button: GamepadButton::Menu, pressed: true } if self.scoped => Some((Vec2::zero(), self.menu.first_enabled())),
+                _ => None,
+            };
+            if let Some((pos, hover)) = opened
+            {
+                event.used = true;
+                self.open = true;
+                self.pos = self.origin + pos;
+                self.levels = vec![hover];
+                self.pressed = false;
+                ctx.request.paint();
+            }
+        }
+        self.child.event(ctx, data);
+    }
+
+    impl_layout_inquire_child!(T);
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, data: &T, size: Vec2) -> Vec2
+    {
+        self.size = self.child.layout_compute(ctx, data, size);
+        self.size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
+    {
+        self.origin = ctx.origin();
+        let mark = ctx.focus.mark();
+        self.child.paint(ctx, data);
+        self.scoped = ctx.focus.current_since(mark);
+        self.panels.clear();
+        if !self.open { return; }
+        let screen = ctx.screen_size();
+        let mut menu = &self.menu;
+        let mut anchor = self.pos;
+        for (level, hover) in self.levels.iter().enumerate()
+        {
+            let size = panel_size(ctx, menu);
+            //keep it on screen, submenus flip to the left of their parent
+            let mut min = anchor;
+            if min.0 + size.0 > screen.0 { min.0 = if level > 0 { self.panels[level - 1].min.0 - size.0 } else { screen.0 - size.0 }; }
+            min = Vec2(min.0.min(screen.0 - size.0).max(0.0), min.1.min(screen.1 - size.1).max(0.0));
+            let panel = Rect::new_size(min, size);
+            self.panels.push(panel);
+            paint_panel(ctx, menu, *hover, panel);
+            //descend into the hovered submenu
+            let Some(index) = *hover else { break; };
+            let EntryKind::Submenu(submenu) = &menu.entries[index].kind else { break; };
+            if level + 1 == self.levels.len() { break; }
+            anchor = Vec2(panel.max.0, panel.min.1 + index as f32 * ROW_HEIGHT);
+            menu = submenu;
+        }
+    }
+}
+
+impl<T, E: Clone, W: Widget<T, E>> ContextMenu<T, E, W>
+{
+    pub fn new(widget: W, menu: Menu<E>) -> Self
+    {
+        Self
+        {
+            child: widget,
+            menu,
+            open: false,
+            pos: Vec2::zero(),
+            levels: Vec::new(),
+            pressed: false,
+            _phantom: PhantomData,
+            size: Vec2::zero(),
+            origin: Vec2::zero(),
+            scoped: false,
+            panels: Vec::new(),
+        }
+    }
+
+    //the panel at the given level
+    fn panel_menu(&self, level: usize) -> &Menu<E>
+    {
+        let mut menu = &self.menu;
+        for hover in &self.levels[..level]
+        {
+            if let Some(index) = hover && let EntryKind::Submenu(submenu) = &menu.entries[*index].kind { menu = submenu; }
+        }
+        menu
+    }
+
+    //hovers the entry, opening it if it is a submenu
+    fn hover(&mut self, level: usize, index: Option<usize>)
+    {
+        //the panels of closed submenus are gone before the next paint, so the pointer cannot find them any more
+        self.levels.truncate(level + 1);
+        self.panels.truncate(level + 1);
+        self.levels[level] = index;
+        let submenu = index.map(|index| &self.panel_menu(level).entries[index]).is_some_and(|entry| entry.enabled && matches!(entry.kind, EntryKind::Submenu(_)));
+        if submenu { self.levels.push(None); }
+    }
+
+    fn close(&mut self)
+    {
+        self.open = false;
+        self.levels.clear();
+        self.panels.clear();
+        self.pressed = false;
+    }
+
+    fn choose(&mut self, ctx: &mut EventCtx<E>, level: usize, index: usize)
+    {
+        let entry = &self.panel_menu(level).entries[index];
+        if !entry.enabled { return; }
+        match &entry.kind
+        {
+            EntryKind::Action(tag) =>
+            {
+                ctx.emit(LogicEvent::Chosen(tag.clone()));
+                self.close();
+            },
+            EntryKind::Submenu(submenu) =>
+            {
+                let first = submenu.first_enabled();
+                self.hover(level, Some(index));
+                if let Some(last) = self.levels.last_mut() { *last = first; }
+            },
+        }
+    }
+
+    //layer pass, positions are absolute
+    fn menu_event(&mut self, ctx: &mut EventCtx<E>)
+    {
+        let WidgetEvent::Layer(event) = &mut ctx.event else { return; };
+        let row = event.event.pos().and_then(|pos| self.panels.iter().enumerate().rev().find(|(_, panel)| panel.contains_linf(pos)).map(|(level, panel)| (level, pos, *panel)))
+            .map(|(level, pos, panel)| (level, (((pos.1 - panel.min.1) / ROW_HEIGHT) as usize).min(self.panel_menu(level).entries.len().saturating_sub(1))));
+        let last = self.levels.len() - 1;
+        let mut used = true;
+        let mut choose = None;
+        match event.event
+        {
+            HardwareEvent::PointerMoved { .. } => match row
+            {
+                Some((level, index)) => if self.levels.get(level) != Some(&Some(index))
+                {
+                    self.hover(level, Some(index));
+                    ctx.request.paint();
+                },
+                None => used = false,
+            },
+            HardwareEvent::PointerClicked { button, pressed, .. } => match (row, pressed)
+            {
+                (Some(_), true) => self.pressed = true,
+                (Some((level, index)), false) =>
+                {
+                    if self.pressed && button == MouseButton::Primary { choose = Some((level, index)); }
+                    self.pressed = false;
+                },
+                (None, true) => self.close(),
+                (None, false) =>
+                {
+                    self.pressed = false;
+                    used = false;
+                },
+            },
+            HardwareEvent::Key { key: Key::Up, pressed } | HardwareEvent::GamepadButton { button: GamepadButton::DPadUp, pressed } =>
+            {
+                if pressed { self.levels[last] = self.panel_menu(last).step(self.levels[last], false); }
+            },
+            HardwareEvent::Key { key: Key::Down, pressed } | HardwareEvent::GamepadButton { button: GamepadButton::DPadDown, pressed } =>
+            {
+                if pressed { self.levels[last] = self.panel_menu(last).step(self.levels[last], true); }
+            },
+            HardwareEvent::Key { key: Key::Right, pressed } | HardwareEvent::GamepadButton { button: GamepadButton::DPadRight, pressed } =>
+            {
+                if pressed && let Some(index) = self.levels[last] && let EntryKind::Submenu(_) = self.panel_menu(last).entries[index].kind { choose = Some((last, index)); }
+            },
+            HardwareEvent::Key { key: Key::Return, pressed } | HardwareEvent::GamepadButton { button: GamepadButton::South, pressed } =>
+            {
+                if pressed && let Some(index) = self.levels[last] { choose = Some((last, index)); }
+            },
+            //back closes the innermost submenu, cancel also closes the whole menu
+            HardwareEvent::Key { key: Key::Left | Key::Escape, pressed } | HardwareEvent::GamepadButton { button: GamepadButton::DPadLeft | GamepadButton::East, pressed } =>
+            {
+                let back = matches!(event.event, HardwareEvent::Key { key: Key::Left, .. } | HardwareEvent::GamepadButton { button: GamepadButton::DPadLeft, .. });
+                if pressed && self.levels.len() > 1
+                {
+                    self.levels.pop();
+                    self.panels.truncate(self.levels.len());
+                } else if pressed && !back { self.close(); }
+            },
+            _ => used = false,
+        }
+        if used
+        {
+            event.used = true;
+            ctx.request.paint();
+        }
+        if let Some((level, index)) = choose { self.choose(ctx, level, index); }
+    }
+}
+
+fn panel_size<E>(ctx: &mut PaintCtx, menu: &Menu<E>) -> Vec2
+{
+    let (mut label, mut shortcut, mut arrow) = (0.0f32, 0.0f32, 0.0f32);
+    for entry in &menu.entries
+    {
+        label = label.max(ctx.painter.text_width(&entry.label, ROW_HEIGHT));
+        if let Some(chord) = entry.shortcut { shortcut = shortcut.max(GAP + ctx.painter.text_width(&chord.to_string(), ROW_HEIGHT)); }
+        if let EntryKind::Submenu(_) = entry.kind { arrow = GAP + ctx.painter.text_width(ARROW, ROW_HEIGHT); }
+    }
+    Vec2(label + shortcut.max(arrow) + 2.0 * PADDING, menu.entries.len() as f32 * ROW_HEIGHT)
+}
+
+fn paint_panel<E>(ctx: &mut PaintCtx, menu: &Menu<E>, hover: Option<usize>, panel: Rect)
+{
+    let size = panel.size();
+    ctx.painter.push_top_layer(panel.min);
+    ctx.record("Menu", None, size, true);
+    ctx.access_push(Role::Group, size);
+    ctx.painter.draw_rect(Rect::new_origin(size), ctx.style.bg.cold);
+    ctx.painter.draw_outline(Rect::new_origin(size), PADDING / 3.0, ctx.style.top);
+    for (index, entry) in menu.entries.iter().enumerate()
+    {
+        let row = Rect { min: Vec2(0.0, index as f32 * ROW_HEIGHT), max: Vec2(size.0, (index + 1) as f32 * ROW_HEIGHT) };
+        if hover == Some(index) && entry.enabled { ctx.painter.draw_rect(row, ctx.style.bg.hover); }
+        let mut color = ctx.style.text;
+        if !entry.enabled { color.a *= 0.5; }
+        let text = Rect { min: row.min + Vec2(PADDING, 0.0), max: row.max - Vec2(PADDING, 0.0) };
+        ctx.painter.draw_text(text, &entry.label, ROW_HEIGHT, text::Align::Left, false, color);
+        let hint = match &entry.kind
+        {
+            EntryKind::Submenu(_) => Some(ARROW.to_string()),
+            EntryKind::Action(_) => entry.shortcut.map(|chord| chord.to_string()),
+        };
+        //right aligned
+        if let Some(hint) = hint
+        {
+            let width = ctx.painter.text_width(&hint, ROW_HEIGHT);
+            ctx.painter.draw_text(Rect { min: Vec2(text.max.0 - width, text.min.1), max: text.max }, &hint, ROW_HEIGHT, text::Align::Left, false, color);
+        }
+        if ctx.accessible()
+        {
+            ctx.add_offset(row.min);
+            ctx.access_leaf(Role::Button, &entry.label, entry.shortcut.map(|chord| chord.to_string()), None, row.size());
+            ctx.add_offset(-row.min);
+        }
+    }
+    ctx.access_pop();
+    ctx.painter.pop_layer();
+}
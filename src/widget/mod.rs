@@ -1,5 +1,5 @@
-use super::{*, math::{Vec2, Rect}, text, event::{HardwareEvent, SyntheticEvent, WidgetEvent, LogicEvent, MouseButton, Key}, lens::Lens};
-use std::marker::PhantomData;
+use super::{*, math::{Vec2, Rect}, text, event::{HardwareEvent, SyntheticEvent, WidgetEvent, LogicEvent, MouseButton, Key, KeyChord, GamepadButton, TouchPhase}, lens::Lens, state::{Persistent, WidgetId}, access::Role, cursor::CursorIcon};
+use std::{marker::PhantomData, any::Any};
 use macros::*;
 
 pub mod primitive;
@@ -7,6 +7,8 @@ pub mod data;
 pub mod layout;
 pub mod interact;
 pub mod compose;
+pub mod layer;
+pub mod menu;
 
 pub trait WidgetExt<T, E>: Widget<T, E> + Sized
 {
@@ -21,13 +23,22 @@ pub trait WidgetExt<T, E>: Widget<T, E> + Sized
     fn cache(self) -> data::Cache<T, E, Self> where T: Clone + PartialEq { data::Cache::new(self) }
     //layout
     fn fix(self) -> layout::Fix<T, E, Self> { layout::Fix::new(self) }
+    fn resize(self) -> layout::Resize<T, E, Self> { layout::Resize::new(self) }
     fn align(self) -> layout::Align<T, E, Self> { layout::Align::new(self) }
     fn pad(self) -> layout::Padding<T, E, Self> { layout::Padding::new(self) }
+    fn scroll(self) -> layout::Scroll<T, E, Self> { layout::Scroll::new(self) }
     //interact
     fn response<'a>(self) -> interact::Response<'a, T, E, Self> where E: Clone { interact::Response::new(self) }
+    fn shortcut(self, chord: KeyChord, tag: E) -> interact::Shortcuts<T, E, Self> where E: Clone { interact::Shortcuts::new(self).shortcut(chord, tag) }
+    fn drag_source<'a, P: Any, F: FnMut(&T) -> P + 'a>(self, payload: F) -> interact::DragSource<'a, T, E, Self> { interact::DragSource::new(self, payload) }
+    fn drop_target<'a, P: Any, F: FnMut(&mut T, P) + 'a>(self, drop: F) -> interact::DropTarget<'a, T, E, Self, P> { interact::DropTarget::new(self, drop) }
     //composition
     fn maybe<'a, F: FnMut(&mut T) -> bool + 'a>(self, f: F) -> compose::Maybe<'a, T, E, Self, F> { compose::Maybe::new(self, f) }
     fn and<W2: Widget<T, E>>(self, other: W2) -> compose::And<T, E, Self, W2> { compose::And::new(self, other) }
+    //layer
+    fn overlay<C: Widget<T, E>>(self, content: C) -> layer::Overlay<T, E, Self, C> { layer::Overlay::new(self, content) }
+    fn tooltip<C: Widget<T, E>>(self, content: C) -> layer::Tooltip<T, E, Self, C> { layer::Tooltip::new(self, content) }
+    fn context_menu(self, menu: menu::Menu<E>) -> menu::ContextMenu<T, E, Self> where E: Clone { menu::ContextMenu::new(self, menu) }
 }
 
 impl<T, E, W: Widget<T, E>> WidgetExt<T, E> for W {}
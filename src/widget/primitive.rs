@@ -1,7 +1,13 @@
 use super::*;
 use std::borrow::Borrow;
+use copypasta::{ClipboardContext, ClipboardProvider};
+use crate::paint::{Color, TextureId};
 
 const DEFAULT_LENGTH: f32 = 10.0;
+const FOCUS_WIDTH: f32 = 0.1;
+const BLINK_TIME: f32 = 0.5;
+const UNDERLINE_WIDTH: f32 = 0.05;
+const ERR: f32 = 1e-3;
 
 pub struct Bg<T, E, W: Widget<T, E>>
 {
@@ -25,7 +31,9 @@ impl<T, E, W: Widget<T, E>> Widget<T, E> for Bg<T, E, W>
     #[inline]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
     {
-        ctx.draw_rect(Rect::new_origin(self.size), ctx.style.bg.get(ctx.state));
+        ctx.record("Bg", None, self.size, true);
+        ctx.draw_rect(Rect::new_origin(self.size), ctx.color(&ctx.style.bg));
+        if ctx.is_focused() { ctx.draw_outline(Rect::new_origin(self.size), FOCUS_WIDTH, ctx.style.focus); }
         self.child.paint(ctx, data);
     }
 }
@@ -42,6 +50,7 @@ pub struct Label<T: Borrow<str>>
 {
     text_size: f32,
     size: Vec2,
+    misfit: Option<usize>,
     _phantom: PhantomData<T>
 }
 
@@ -57,11 +66,20 @@ impl<T: Borrow<str>, E> Widget<T, E> for Label<T>
         self.size
     }
 
-    #[inline] fn layout_compute(&mut self, _: &mut LayoutComputeCtx, _: &T, _: Vec2) -> Vec2 { self.size }
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, _: &T, size: Vec2) -> Vec2
+    {
+        self.misfit = None;
+        if self.size.0 > size.0 + ERR || self.size.1 > size.1 + ERR { self.misfit = ctx.does_not_fit("Label", self.size, size); }
+        self.size
+    }
 
     #[inline]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
     {
+        ctx.record("Label", None, self.size, false);
+        if let Some(misfit) = self.misfit { ctx.misfit(misfit); }
+        ctx.access_leaf(Role::Label, data.borrow(), None, None, self.size);
         ctx.draw_text(Rect::new_origin(self.size), data.borrow(), self.text_size, text::Align::Left, false, ctx.style.text);
     }
 }
@@ -70,7 +88,7 @@ impl<T: Borrow<str>> Label<T>
 {
     pub fn new() -> Self
     {
-        Self { text_size: 1.0, size: Vec2::zero(), _phantom: PhantomData }
+        Self { text_size: 1.0, size: Vec2::zero(), misfit: None, _phantom: PhantomData }
     }
 
     pub fn size(mut self, text_size: f32) -> Self
@@ -80,6 +98,82 @@ impl<T: Borrow<str>> Label<T>
     }
 }
 
+pub struct Image
+{
+    height: Option<f32>,
+    aspect: Option<f32>,
+    uv: Rect,
+    tint: Color,
+    size: Vec2,
+    misfit: Option<usize>
+}
+
+impl<E> Widget<TextureId, E> for Image
+{
+    impl_event_empty!(TextureId);
+
+    #[inline]
+    fn layout_inquire(&mut self, ctx: &mut LayoutInquireCtx, data: &TextureId) -> Vec2
+    {
+        //the texture decides whatever was not fixed
+        let natural = ctx.texture_size(*data).map(|size| Vec2(size.0 * (self.uv.max.0 - self.uv.min.0).abs(), size.1 * (self.uv.max.1 - self.uv.min.1).abs())).unwrap_or(Vec2(1.0, 1.0));
+        let aspect = self.aspect.unwrap_or(natural.0 / natural.1.max(ERR));
+        let height = self.height.unwrap_or(natural.1);
+        self.size = Vec2(height * aspect, height);
+        self.size
+    }
+
+    #[inline]
+    fn layout_compute(&mut self, ctx: &mut LayoutComputeCtx, _: &TextureId, size: Vec2) -> Vec2
+    {
+        self.misfit = None;
+        if self.size.0 > size.0 + ERR || self.size.1 > size.1 + ERR { self.misfit = ctx.does_not_fit("Image", self.size, size); }
+        self.size
+    }
+
+    #[inline]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &TextureId)
+    {
+        ctx.record("Image", None, self.size, false);
+        if let Some(misfit) = self.misfit { ctx.misfit(misfit); }
+        ctx.draw_image(Rect::new_origin(self.size), *data, self.uv, self.tint);
+    }
+}
+
+impl Image
+{
+    pub fn new() -> Self
+    {
+        Self { height: None, aspect: None, uv: Rect { min: Vec2(0.0, 0.0), max: Vec2(1.0, 1.0) }, tint: Color::from_discrete_srgb(255, 255, 255, 255), size: Vec2::zero(), misfit: None }
+    }
+
+    pub fn height(mut self, height: f32) -> Self
+    {
+        self.height = Some(height);
+        self
+    }
+
+    //width / height
+    pub fn aspect(mut self, aspect: f32) -> Self
+    {
+        self.aspect = Some(aspect);
+        self
+    }
+
+    //part of the texture in 0..1, e.g. one icon of a sprite sheet
+    pub fn uv(mut self, uv: Rect) -> Self
+    {
+        self.uv = uv;
+        self
+    }
+
+    pub fn tint(mut self, tint: Color) -> Self
+    {
+        self.tint = tint;
+        self
+    }
+}
+
 pub struct Text<T: Borrow<str>>
 {
     text_size: f32,
@@ -113,6 +207,8 @@ impl<T: Borrow<str>, E> Widget<T, E> for Text<T>
     #[inline]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T)
     {
+        ctx.record("Text", None, self.actual_size, false);
+        ctx.access_leaf(Role::Label, data.borrow(), None, None, self.actual_size);
         ctx.draw_text(Rect::new_origin(self.actual_size), data.borrow(), self.text_size, self.align, true, ctx.style.text);
     }
 }
@@ -159,10 +255,12 @@ impl<E> Widget<bool, E> for Check
     fn paint(&mut self, ctx: &mut PaintCtx, data: &bool)
     {
         let size1 = self.size;
+        ctx.record("Check", None, Vec2(size1, size1), true);
+        ctx.access_leaf(Role::CheckBox, "", None, Some(*data), Vec2(size1, size1));
         let (size2, size3) = (size1 * 0.15, size1 * 0.7);
         let (size4, size5) = (size1 * 0.3, size1 * 0.4);
         ctx.painter.draw_rect(Rect::new_origin(Vec2(size1, size1)), ctx.style.top);
-        ctx.painter.draw_rect(Rect::new_size(Vec2(size2, size2), Vec2(size3, size3)), ctx.style.data.get(ctx.state));
+        ctx.painter.draw_rect(Rect::new_size(Vec2(size2, size2), Vec2(size3, size3)), ctx.color(&ctx.style.data));
         if *data { ctx.painter.draw_rhombus(Rect::new_size(Vec2(size4, size4), Vec2(size5, size5)), ctx.style.top); }
     }
 }
@@ -223,7 +321,7 @@ pub struct Slider
     step: f32,
     wish_size: Vec2,
     actual_size: Vec2,
-    dragged: bool,
+    dragged: Persistent<bool>,
 }
 
 impl<E> Widget<f32, E> for Slider
@@ -244,7 +342,7 @@ impl<E> Widget<f32, E> for Slider
                         {
                             if pos.0 >= -0.5 && pos.0 <= size.0 + 0.5 //relaxed width bound
                             {
-                                self.dragged = true;
+                                self.dragged.set(true);
                                 event.used = true;
                             }
                             let f = pos.0 / size.0;
@@ -254,19 +352,19 @@ impl<E> Widget<f32, E> for Slider
                                 ctx.request.paint();
                             }
                         }
-                    } else if self.dragged
+                    } else if self.dragged.get()
                     {
-                        self.dragged = false;
+                        self.dragged.set(false);
                         ctx.request.paint();
                         event.used = true;
                     }                    
                 },
-                HardwareEvent::PointerGone => if self.dragged
+                HardwareEvent::PointerGone => if self.dragged.get()
                 {
-                    self.dragged = false;
+                    self.dragged.set(false);
                     ctx.request.paint();
                 },
-                HardwareEvent::PointerMoved { pos, .. } => if self.dragged
+                HardwareEvent::PointerMoved { pos, .. } => if self.dragged.get()
                 {
                     let f = (pos.0 / size.0).max(0.0).min(1.0);
                     let new = (f * (self.max - self.min) / self.step).round() * self.step + self.min;
@@ -289,6 +387,13 @@ impl<E> Widget<f32, E> for Slider
                 _ => {},
             }
         }
+        //open hand over the slider, closed one while dragging
+        let pointer = match &ctx.event { WidgetEvent::Hardware(event) => event.event.pos(), _ => None };
+        if let Some(pos) = pointer
+        {
+            if self.dragged.get() { ctx.set_cursor(CursorIcon::Grabbing); }
+            else if Rect::new_origin(size).contains_linf(pos) { ctx.set_cursor(CursorIcon::Grab); }
+        }
     }
 
     #[inline] fn layout_inquire(&mut self, _: &mut LayoutInquireCtx, _: &f32) -> Vec2 { self.wish_size }
@@ -304,11 +409,13 @@ impl<E> Widget<f32, E> for Slider
     fn paint(&mut self, ctx: &mut PaintCtx, data: &f32)
     {
         let size = self.actual_size;
+        ctx.record("Slider", None, size, true);
+        if ctx.accessible() { ctx.access_leaf(Role::Slider, "", Some(data.to_string()), None, size); }
         let pos = (data - self.min) / (self.max - self.min) * size.0;
         let (x0, x1, x2, x3) = (0.0, pos - 0.5 * size.1, pos + 0.5 * size.1, size.0);
         let (y0, y1, y2, y3) = (0.0, size.1 / 3.0, size.1 / 1.5, size.1);
         ctx.painter.draw_rect(Rect { min: Vec2(x0, y1), max: Vec2(x3, y2) }, ctx.style.top);
-        ctx.painter.draw_rhombus(Rect { min: Vec2(x1, y0), max: Vec2(x2, y3) }, if self.dragged { ctx.style.data.hot } else { ctx.style.data.get(ctx.state) });
+        ctx.painter.draw_rhombus(Rect { min: Vec2(x1, y0), max: Vec2(x2, y3) }, if self.dragged.get() { ctx.style.data.hot } else { ctx.color(&ctx.style.data) });
     }
 }
 
@@ -316,7 +423,7 @@ impl Slider
 {
     pub fn new() -> Self
     {
-        Self { min: 0.0, max: 1.0, step: 0.1, wish_size: Vec2(DEFAULT_LENGTH, 1.0), actual_size: Vec2::zero(), dragged: false }
+        Self { min: 0.0, max: 1.0, step: 0.1, wish_size: Vec2(DEFAULT_LENGTH, 1.0), actual_size: Vec2::zero(), dragged: Persistent::new(false) }
     }
 
     pub fn min(mut self, min: f32) -> Self
@@ -348,6 +455,12 @@ impl Slider
         self.wish_size.1 = height;
         self
     }
+
+    pub fn persist(mut self, dragged: Persistent<bool>) -> Self
+    {
+        self.dragged = dragged;
+        self
+    }
 }
 
 pub struct VSlider
@@ -357,7 +470,7 @@ pub struct VSlider
     step: f32,
     wish_size: Vec2,
     actual_size: Vec2,
-    dragged: bool,
+    dragged: Persistent<bool>,
 }
 
 impl<E> Widget<f32, E> for VSlider
@@ -378,7 +491,7 @@ impl<E> Widget<f32, E> for VSlider
                         {
                             if pos.1 >= -0.5 && pos.1 <= size.1 + 0.5 //relaxed height bound
                             {
-                                self.dragged = true;
+                                self.dragged.set(true);
                                 event.used = true;
                             }
                             let f = pos.1 / size.1;
@@ -388,19 +501,19 @@ impl<E> Widget<f32, E> for VSlider
                                 ctx.request.paint();
                             }
                         }
-                    } else if self.dragged
+                    } else if self.dragged.get()
                     {
-                        self.dragged = false;
+                        self.dragged.set(false);
                         ctx.request.paint();
                         event.used = true;
                     }
                 },
-                HardwareEvent::PointerGone => if self.dragged
+                HardwareEvent::PointerGone => if self.dragged.get()
                 {
-                    self.dragged = false;
+                    self.dragged.set(false);
                     ctx.request.paint();
                 },
-                HardwareEvent::PointerMoved { pos, .. } => if self.dragged
+                HardwareEvent::PointerMoved { pos, .. } => if self.dragged.get()
                 {
                     let f = (pos.1 / size.1).max(0.0).min(1.0);
                     let new = (f * (self.max - self.min) / self.step).round() * self.step + self.min;
@@ -422,6 +535,13 @@ impl<E> Widget<f32, E> for VSlider
                 _ => {},
             }
         }
+        //open hand over the slider, closed one while dragging
+        let pointer = match &ctx.event { WidgetEvent::Hardware(event) => event.event.pos(), _ => None };
+        if let Some(pos) = pointer
+        {
+            if self.dragged.get() { ctx.set_cursor(CursorIcon::Grabbing); }
+            else if Rect::new_origin(size).contains_linf(pos) { ctx.set_cursor(CursorIcon::Grab); }
+        }
     }
 
     #[inline] fn layout_inquire(&mut self, _: &mut LayoutInquireCtx, _: &f32) -> Vec2 { self.wish_size }
@@ -437,11 +557,13 @@ impl<E> Widget<f32, E> for VSlider
     fn paint(&mut self, ctx: &mut PaintCtx, data: &f32)
     {
         let size = self.actual_size;
+        ctx.record("VSlider", None, size, true);
+        if ctx.accessible() { ctx.access_leaf(Role::Slider, "", Some(data.to_string()), None, size); }
         let pos = (data - self.min) / (self.max - self.min) * size.1;
         let (x0, x1, x2, x3) = (0.0, size.0 / 3.0, size.0 / 1.5, size.0);
         let (y0, y1, y2, y3) = (0.0, pos - 0.5 * size.0, pos + 0.5 * size.0, size.1);
         ctx.painter.draw_rect(Rect { min: Vec2(x1, y0), max: Vec2(x2, y3) }, ctx.style.top);
-        ctx.painter.draw_rhombus(Rect { min: Vec2(x0, y1), max: Vec2(x3, y2) }, if self.dragged { ctx.style.data.hot } else { ctx.style.data.get(ctx.state) });
+        ctx.painter.draw_rhombus(Rect { min: Vec2(x0, y1), max: Vec2(x3, y2) }, if self.dragged.get() { ctx.style.data.hot } else { ctx.color(&ctx.style.data) });
     }
 }
 
@@ -449,7 +571,7 @@ impl VSlider
 {
     pub fn new() -> Self
     {
-        Self { min: 0.0, max: 1.0, step: 0.1, wish_size: Vec2(1.0, DEFAULT_LENGTH), actual_size: Vec2::zero(), dragged: false }
+        Self { min: 0.0, max: 1.0, step: 0.1, wish_size: Vec2(1.0, DEFAULT_LENGTH), actual_size: Vec2::zero(), dragged: Persistent::new(false) }
     }
 
     pub fn min(mut self, min: f32) -> Self
@@ -481,15 +603,25 @@ impl VSlider
         self.wish_size.1 = height;
         self
     }
+
+    pub fn persist(mut self, dragged: Persistent<bool>) -> Self
+    {
+        self.dragged = dragged;
+        self
+    }
 }
 
 pub struct Edit<'a>
 {
-    active: bool,
+    active: Persistent<bool>,
     filter: Box<dyn FnMut(char) -> bool + 'a>,
     max_length: Option<usize>,
     wish_size: Vec2,
     actual_size: Vec2,
+    blink_start: f32,
+    //ime composition, shown at the caret until committed, cursor as byte offset into the preedit
+    preedit: String,
+    preedit_cursor: Option<usize>,
 }
 
 impl<'a, E> Widget<String, E> for Edit<'a>
@@ -497,35 +629,80 @@ impl<'a, E> Widget<String, E> for Edit<'a>
     #[inline]
     fn event(&mut self, ctx: &mut EventCtx<E>, data: &mut String)
     {
+        let hover = match &ctx.event
+        {
+            WidgetEvent::Hardware(event) => event.event.pos().is_some_and(|pos| Rect::new_origin(self.actual_size).contains_linf(pos)),
+            _ => false,
+        };
+        if hover { ctx.set_cursor(CursorIcon::Text); }
+        //ctrl+alt is altgr on some platforms and still types characters
+        let command = ctx.modifiers.ctrl && !ctx.modifiers.alt;
         match &mut ctx.event
         {
-            WidgetEvent::Hardware(event) => if self.active && !event.used
+            WidgetEvent::Hardware(event) => if self.active.get() && !event.used
             {
-                if let HardwareEvent::Char(ch) = event.event
+                if let HardwareEvent::Char(ch) = event.event && !command
                 {
                     event.used = true;
-                    if (self.filter)(ch) && self.max_length.map(|max| data.chars().count() < max).unwrap_or(true) { data.push(ch); }
+                    self.insert(data, ch);
+                    self.blink_start = ctx.time;
                     ctx.request.paint();
                 }
-                if let HardwareEvent::Key { key, pressed: true } = event.event
+                match &event.event
                 {
-                    if key == Key::Back
+                    HardwareEvent::ImePreedit { text, cursor } =>
+                    {
+                        event.used = true;
+                        self.preedit = text.clone();
+                        self.preedit_cursor = *cursor;
+                        ctx.request.paint();
+                    },
+                    HardwareEvent::ImeCommit(text) =>
                     {
                         event.used = true;
-                        data.pop();
+                        for ch in text.chars() { self.insert(data, ch); }
+                        self.preedit.clear();
+                        self.preedit_cursor = None;
+                        self.blink_start = ctx.time;
                         ctx.request.paint();
+                    },
+                    _ => {},
+                }
+                if let HardwareEvent::Key { key, pressed: true } = event.event
+                {
+                    match key
+                    {
+                        Key::Back => { data.pop(); },
+                        Key::C if command => copy(data),
+                        Key::X if command =>
+                        {
+                            copy(data);
+                            data.clear();
+                        },
+                        Key::V if command => self.paste(data),
+                        _ => return,
                     }
+                    event.used = true;
+                    self.blink_start = ctx.time;
+                    ctx.request.paint();
                 }
             },
+            WidgetEvent::Synthetic(SyntheticEvent::FocusGained) =>
+            {
+                self.active.set(true);
+                self.blink_start = ctx.time;
+                ctx.request.paint();
+            },
+            WidgetEvent::Synthetic(SyntheticEvent::FocusLost) =>
+            {
+                self.active.set(false);
+                self.preedit.clear();
+                ctx.request.paint();
+            },
             WidgetEvent::Synthetic(SyntheticEvent::Clicked(button)) =>
             {
-                self.active = button.is_some();
-                if let Some(MouseButton::Secondary) = button
-                {
-                    use copypasta::{ClipboardContext, ClipboardProvider};
-                    let mut ctx = ClipboardContext::new().unwrap();
-                    if let Ok(contents) = ctx.get_contents() { for ch in contents.chars().filter(|ch| (self.filter)(*ch)) { data.push(ch); } }
-                }
+                self.active.set(button.is_some());
+                if let Some(MouseButton::Secondary) = button { self.paste(data); }
                 ctx.request.paint();
             },
             _ => {},
@@ -545,18 +722,54 @@ impl<'a, E> Widget<String, E> for Edit<'a>
     fn paint(&mut self, ctx: &mut PaintCtx, data: &String)
     {
         let size = self.actual_size;
+        ctx.record("Edit", None, size, true);
+        if ctx.accessible() { ctx.access_leaf(Role::TextField, "", Some(data.clone()), None, size); }
         let rect = Rect::new_origin(size);
-        ctx.painter.draw_rect(rect, ctx.style.data.get(ctx.state));
-        let display_data = data.clone() + if self.active && self.max_length.map_or(true, |ml| data.len() < ml) { "_" } else { "" };
+        ctx.painter.draw_rect(rect, ctx.color(&ctx.style.data));
+        //keep the caret blinking
+        if self.active.get() { ctx.animate(); }
+        let blink = ((ctx.time() - self.blink_start) / BLINK_TIME) as u32 % 2 == 0;
+        let composing = self.active.get() && !self.preedit.is_empty();
+        let display_data = if composing { data.clone() + &self.preedit } else { data.clone() + if self.active.get() && blink && self.max_length.map_or(true, |ml| data.len() < ml) { "_" } else { "" } };
         ctx.painter.draw_text(rect, &display_data, size.1, text::Align::Left, false, ctx.style.text);
+        if self.active.get()
+        {
+            //underline the composition and tell the ime where the caret is
+            let start = ctx.painter.text_width(data, size.1);
+            let mut caret = start;
+            if composing
+            {
+                let end = ctx.painter.text_width(&display_data, size.1);
+                ctx.painter.draw_rect(Rect { min: Vec2(start, size.1 - UNDERLINE_WIDTH), max: Vec2(end, size.1) }, ctx.style.text);
+                let cursor = self.preedit_cursor.unwrap_or(self.preedit.len());
+                caret += ctx.painter.text_width(self.preedit.get(..cursor).unwrap_or(&self.preedit), size.1);
+            }
+            ctx.set_ime_caret(Rect { min: Vec2(caret, 0.0), max: Vec2(caret, size.1) });
+        }
     }
 }
 
+fn copy(text: &str)
+{
+    if let Ok(mut clipboard) = ClipboardContext::new() { let _ = clipboard.set_contents(text.to_owned()); }
+}
+
 impl<'a> Edit<'a>
 {
     pub fn new() -> Self
     {
-        Self { active: false, filter: Box::new(|_| true), max_length: None, wish_size: Vec2(DEFAULT_LENGTH, 1.0), actual_size: Vec2::zero() }
+        Self { active: Persistent::new(false), filter: Box::new(|_| true), max_length: None, wish_size: Vec2(DEFAULT_LENGTH, 1.0), actual_size: Vec2::zero(), blink_start: 0.0, preedit: String::new(), preedit_cursor: None }
+    }
+
+    fn insert(&mut self, data: &mut String, ch: char)
+    {
+        if (self.filter)(ch) && self.max_length.map(|max| data.chars().count() < max).unwrap_or(true) { data.push(ch); }
+    }
+
+    fn paste(&mut self, data: &mut String)
+    {
+        let Ok(mut clipboard) = ClipboardContext::new() else { return; };
+        if let Ok(contents) = clipboard.get_contents() { for ch in contents.chars() { self.insert(data, ch); } }
     }
 
     pub fn filter(mut self, filter: impl FnMut(char) -> bool + 'a) -> Self
@@ -582,4 +795,10 @@ impl<'a> Edit<'a>
         self.wish_size.1 = height;
         self
     }
+
+    pub fn persist(mut self, active: Persistent<bool>) -> Self
+    {
+        self.active = active;
+        self
+    }
 }
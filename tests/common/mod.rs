@@ -0,0 +1,7 @@
+use gru_ui::text::Font;
+
+//DejaVu Sans Mono, license next to it
+pub fn font() -> Font
+{
+    Font::new(include_bytes!("../res/DejaVuSansMono.ttf"))
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use gru_ui::{Widget, math::Vec2, event::{Event, HardwareEvent, LogicEvent, MouseButton}, testing::Harness, widget::{WidgetExt, layout::{Flex, Empty}}};
+
+fn square(tag: &'static str) -> impl Widget<Vec<u32>, &'static str>
+{
+    Empty.fix().width(2.0).height(2.0).response().event(tag)
+}
+
+//two clickable sources handing out 7 and 0 next to a target that only takes what is not 0, each 40 pixels wide
+fn harness() -> Harness<'static, Vec<u32>, &'static str>
+{
+    let mut harness = Harness::new(common::font(), Vec2(200.0, 100.0), |_, _|
+    {
+        Flex::row()
+            .with(square("seven").drag_source(|_: &Vec<u32>| 7u32))
+            .with(square("zero").drag_source(|_: &Vec<u32>| 0u32))
+            .with(square("target").drop_target(|data: &mut Vec<u32>, payload: u32| data.push(payload)).accept(|_, payload: &u32| *payload != 0))
+            .boxed()
+    });
+    harness.frame(&mut Vec::new());
+    harness
+}
+
+//moves the pointer from one place to another with the button held, returns the clicks
+fn drag(harness: &mut Harness<Vec<u32>, &'static str>, data: &mut Vec<u32>, from: Vec2, to: Vec2) -> Vec<&'static str>
+{
+    harness.pointer_move(from).event(HardwareEvent::PointerClicked { pos: from, button: MouseButton::Primary, pressed: true });
+    harness.frame(data);
+    harness.pointer_move(to).frame(data);
+    harness.event(HardwareEvent::PointerClicked { pos: to, button: MouseButton::Primary, pressed: false });
+    harness.frame(data).events.iter().filter_map(|event| match event { Event::Logic(LogicEvent::Clicked(tag, _)) => Some(*tag), _ => None }).collect()
+}
+
+#[test]
+fn threshold()
+{
+    //a little wobble is still a click
+    let mut harness = harness();
+    let mut data = Vec::new();
+    assert_eq!(drag(&mut harness, &mut data, Vec2(20.0, 20.0), Vec2(25.0, 20.0)), ["seven"]);
+    //further it is a drag and the source is not clicked even when released on itself
+    assert!(drag(&mut harness, &mut data, Vec2(20.0, 20.0), Vec2(35.0, 20.0)).is_empty());
+    assert!(data.is_empty());
+}
+
+#[test]
+fn dropped()
+{
+    let mut harness = harness();
+    let mut data = Vec::new();
+    assert!(drag(&mut harness, &mut data, Vec2(20.0, 20.0), Vec2(100.0, 20.0)).is_empty());
+    assert_eq!(data, [7]);
+}
+
+#[test]
+fn rejected()
+{
+    let mut harness = harness();
+    let mut data = Vec::new();
+    assert!(drag(&mut harness, &mut data, Vec2(60.0, 20.0), Vec2(100.0, 20.0)).is_empty());
+    assert!(data.is_empty());
+}
+
+#[test]
+fn cleared_without_a_target()
+{
+    let mut harness = harness();
+    let mut data = Vec::new();
+    drag(&mut harness, &mut data, Vec2(20.0, 20.0), Vec2(150.0, 80.0));
+    //a release on the target afterwards finds nothing to drop
+    drag(&mut harness, &mut data, Vec2(150.0, 80.0), Vec2(100.0, 20.0));
+    assert!(data.is_empty());
+}
@@ -0,0 +1,216 @@
+mod common;
+
+use gru_ui::{Widget, EventCtx, LayoutInquireCtx, LayoutComputeCtx, PaintCtx, math::Vec2, event::{Event, LogicEvent, HardwareEvent, Key, KeyChord, MouseButton, GamepadButton, GamepadAxis, WidgetEvent, SyntheticEvent}, lens::{LensTuple0, LensTuple1}, state::WidgetId, testing::Harness, widget::{WidgetExt, primitive::Edit, layout::{Flex, Empty}}};
+use std::{cell::RefCell, rc::Rc};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+//a unit square that writes down the focus changes it hears
+struct Probe
+{
+    name: &'static str,
+    log: Log,
+}
+
+impl Widget<(), ()> for Probe
+{
+    fn event(&mut self, ctx: &mut EventCtx<()>, _: &mut ())
+    {
+        match ctx.event
+        {
+            WidgetEvent::Synthetic(SyntheticEvent::FocusGained) => self.log.borrow_mut().push(format!("{} gained", self.name)),
+            WidgetEvent::Synthetic(SyntheticEvent::FocusLost) => self.log.borrow_mut().push(format!("{} lost", self.name)),
+            _ => {},
+        }
+    }
+
+    fn layout_inquire(&mut self, _: &mut LayoutInquireCtx, _: &()) -> Vec2 { Vec2(1.0, 1.0) }
+    fn layout_compute(&mut self, _: &mut LayoutComputeCtx, _: &(), _: Vec2) -> Vec2 { Vec2(1.0, 1.0) }
+    fn paint(&mut self, _: &mut PaintCtx, _: &()) {}
+}
+
+const NAMES: [&str; 3] = ["a", "b", "c"];
+
+//three focusable probes in a column
+fn probes(log: Log) -> Harness<'static, (), ()>
+{
+    let mut harness = Harness::new(common::font(), Vec2(100.0, 100.0), move |_, _|
+    {
+        let mut column = Flex::column();
+        for name in NAMES { column = column.with(Probe { name, log: log.clone() }.response().focusable(WidgetId::new(name))); }
+        column.boxed()
+    });
+    harness.frame(&mut ());
+    harness
+}
+
+fn focused(harness: &mut Harness<(), ()>) -> Option<&'static str>
+{
+    let current = harness.ui().focus();
+    NAMES.into_iter().find(|name| current == Some(WidgetId::new(name)))
+}
+
+#[test]
+fn tab_order()
+{
+    let mut harness = probes(Log::default());
+    for expected in ["a", "b", "c", "a"]
+    {
+        harness.key(Key::Tab).frame(&mut ());
+        assert_eq!(focused(&mut harness), Some(expected));
+    }
+    for expected in ["c", "b"]
+    {
+        harness.chord(KeyChord::new(Key::Tab).shift()).frame(&mut ());
+        assert_eq!(focused(&mut harness), Some(expected));
+    }
+}
+
+#[test]
+fn focus_events_reach_the_child()
+{
+    let log = Log::default();
+    let mut harness = probes(log.clone());
+    harness.key(Key::Tab).frame(&mut ());
+    harness.key(Key::Tab).frame(&mut ());
+    assert_eq!(*log.borrow(), ["a gained", "a lost", "b gained"]);
+}
+
+#[test]
+fn set_focus()
+{
+    let log = Log::default();
+    let mut harness = probes(log.clone());
+    harness.ui().set_focus(Some(WidgetId::new("c")));
+    harness.frame(&mut ());
+    assert_eq!(focused(&mut harness), Some("c"));
+    harness.ui().set_focus(None);
+    harness.frame(&mut ());
+    assert_eq!(focused(&mut harness), None);
+    assert_eq!(*log.borrow(), ["c gained", "c lost"]);
+}
+
+#[test]
+fn text_goes_to_the_focused_edit()
+{
+    //the first edit is activated by a click without taking the focus, the second one is focused
+    let mut harness: Harness<(String, String), ()> = Harness::new(common::font(), Vec2(200.0, 100.0), |_, _|
+    {
+        Flex::column()
+            .with(Edit::new().response().lens(LensTuple0))
+            .with(Edit::new().response().focusable(WidgetId::new("focused")).lens(LensTuple1))
+            .boxed()
+    });
+    let mut data = (String::new(), String::new());
+    harness.frame(&mut data);
+    harness.click(Vec2(10.0, 10.0), MouseButton::Primary).frame(&mut data);
+    harness.ui().set_focus(Some(WidgetId::new("focused")));
+    harness.frame(&mut data);
+    harness.text("hi").frame(&mut data);
+    assert_eq!(data, (String::new(), "hi".to_string()));
+    //without a focus the active edit types again
+    harness.ui().set_focus(None);
+    harness.frame(&mut data);
+    harness.click(Vec2(10.0, 10.0), MouseButton::Primary).frame(&mut data);
+    harness.event(HardwareEvent::Char('!')).frame(&mut data);
+    assert_eq!(data, ("!".to_string(), "hi".to_string()));
+}
+
+const GRID: [&str; 4] = ["a", "b", "c", "d"];
+
+//a b
+//c d
+fn grid() -> Harness<'static, (), &'static str>
+{
+    let mut harness = Harness::new(common::font(), Vec2(100.0, 100.0), |_, _|
+    {
+        let cell = |name: &'static str| Empty.fix().width(1.0).height(1.0).response().focusable(WidgetId::new(name)).event(name);
+        Flex::column()
+            .with(Flex::row().with(cell("a")).with(cell("b")))
+            .with(Flex::row().with(cell("c")).with(cell("d")))
+            .boxed()
+    });
+    harness.frame(&mut ());
+    harness
+}
+
+fn grid_focus(harness: &mut Harness<(), &'static str>) -> Option<&'static str>
+{
+    let current = harness.ui().focus();
+    GRID.into_iter().find(|name| current == Some(WidgetId::new(name)))
+}
+
+fn button(harness: &mut Harness<(), &'static str>, button: GamepadButton) -> Option<&'static str>
+{
+    harness.event(HardwareEvent::GamepadButton { button, pressed: true });
+    harness.event(HardwareEvent::GamepadButton { button, pressed: false });
+    harness.frame(&mut ());
+    grid_focus(harness)
+}
+
+fn stick(harness: &mut Harness<(), &'static str>, axis: GamepadAxis, value: f32) -> Option<&'static str>
+{
+    harness.event(HardwareEvent::GamepadAxis { axis, value }).frame(&mut ());
+    grid_focus(harness)
+}
+
+#[test]
+fn arrow_keys_in_a_grid()
+{
+    let mut harness = grid();
+    //without a focus the first direction starts at the top left
+    for (key, expected) in [(Key::Right, "a"), (Key::Right, "b"), (Key::Down, "d"), (Key::Left, "c"), (Key::Up, "a"), (Key::Up, "a")]
+    {
+        harness.key(key).frame(&mut ());
+        assert_eq!(grid_focus(&mut harness), Some(expected));
+    }
+}
+
+#[test]
+fn dpad_in_a_grid()
+{
+    let mut harness = grid();
+    harness.ui().set_focus(Some(WidgetId::new("a")));
+    harness.frame(&mut ());
+    assert_eq!(button(&mut harness, GamepadButton::DPadDown), Some("c"));
+    assert_eq!(button(&mut harness, GamepadButton::DPadRight), Some("d"));
+    assert_eq!(button(&mut harness, GamepadButton::DPadUp), Some("b"));
+    assert_eq!(button(&mut harness, GamepadButton::DPadLeft), Some("a"));
+    //east drops the focus
+    assert_eq!(button(&mut harness, GamepadButton::East), None);
+}
+
+#[test]
+fn stick_has_to_come_back_before_it_moves_again()
+{
+    let mut harness = grid();
+    harness.ui().set_focus(Some(WidgetId::new("a")));
+    harness.frame(&mut ());
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickX, 0.8), Some("b"));
+    //held, or only eased off a bit, it stays
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickX, 1.0), Some("b"));
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickX, 0.4), Some("b"));
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickX, -0.8), Some("b"));
+    //back close to the center it is armed again
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickX, 0.1), Some("b"));
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickX, -0.8), Some("a"));
+    //the axes are armed on their own
+    assert_eq!(stick(&mut harness, GamepadAxis::LeftStickY, 0.8), Some("c"));
+}
+
+#[test]
+fn confirm_clicks_the_focused_cell()
+{
+    let mut harness = grid();
+    harness.ui().set_focus(Some(WidgetId::new("d")));
+    harness.frame(&mut ());
+    let clicked = |events: &[Event<&'static str>]| -> Vec<&'static str>
+    {
+        events.iter().filter_map(|event| match event { Event::Logic(LogicEvent::Clicked(tag, MouseButton::Primary)) => Some(*tag), _ => None }).collect()
+    };
+    harness.key(Key::Return);
+    assert_eq!(clicked(harness.frame(&mut ()).events), ["d"]);
+    harness.event(HardwareEvent::GamepadButton { button: GamepadButton::South, pressed: true });
+    harness.event(HardwareEvent::GamepadButton { button: GamepadButton::South, pressed: false });
+    assert_eq!(clicked(harness.frame(&mut ()).events), ["d"]);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use gru_ui::{UiInit, math::Vec2, paint::{TexCoords, TextureId}, testing::{Harness, assert_snapshot}, widget::{WidgetExt, primitive::Image, layout::Flex}};
+
+//image lines of the snapshot, the rest is scroll bars and such
+fn images(snapshot: &str) -> Vec<&str>
+{
+    snapshot.lines().filter(|line| line.starts_with("image")).collect()
+}
+
+//a 40x20 pixel texture in a 5x1.5 unit scroll view, shown at twice its size so the bottom quarter is cut off
+fn scrolled(init: UiInit) -> (Harness<'static, TextureId, ()>, TextureId)
+{
+    let mut harness = Harness::with_init(init, Vec2(100.0, 30.0), |_, _| Image::new().height(2.0).scroll().boxed());
+    let texture = harness.ui().add_texture(40, 20);
+    (harness, texture)
+}
+
+#[test]
+fn row_of_images()
+{
+    let mut harness: Harness<TextureId, ()> = Harness::new(common::font(), Vec2(100.0, 60.0), |_, _| Flex::row().padding(1.0).with(Image::new()).with(Image::new().aspect(1.0)).boxed());
+    let mut texture = harness.ui().add_texture(40, 20);
+    let snapshot = harness.snapshot(&mut texture);
+    assert_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/row_of_images.txt"), &snapshot);
+}
+
+#[test]
+fn sizing()
+{
+    let mut harness: Harness<TextureId, ()> = Harness::new(common::font(), Vec2(200.0, 60.0), |_, _| Flex::row().with(Image::new()).with(Image::new().height(2.0)).with(Image::new().aspect(1.0)).boxed());
+    let mut texture = harness.ui().add_texture(40, 20);
+    let snapshot = harness.snapshot(&mut texture);
+    assert_eq!(images(&snapshot),
+    [
+        //the texture decides everything
+        "image 0.00 0.00 40.00 20.00 (1.000 1.000 1.000 1.000)",
+        //the texture decides the aspect
+        "image 40.00 0.00 120.00 40.00 (1.000 1.000 1.000 1.000)",
+        //the texture decides the height
+        "image 120.00 0.00 140.00 20.00 (1.000 1.000 1.000 1.000)",
+    ]);
+}
+
+#[test]
+fn clipped_on_the_cpu()
+{
+    let (mut harness, mut texture) = scrolled(UiInit::new(common::font()).cpu_clip());
+    let snapshot = harness.snapshot(&mut texture);
+    //the commands carry no scissor, the quads are cut instead
+    assert!(!snapshot.lines().any(|line| line.starts_with("clip ") || line == "unclip"));
+    assert_eq!(images(&snapshot), ["image 0.00 0.00 80.00 30.00 (1.000 1.000 1.000 1.000)"]);
+    //the texture is cut off along with the quad instead of squeezed into it
+    let frame = harness.frame(&mut texture);
+    let max_v = frame.paint.vertices.iter().filter_map(|vertex| match vertex.tex_coords { TexCoords::Texture(_, v, _) => Some(v), _ => None }).fold(0.0, f32::max);
+    assert!((max_v - 0.75).abs() < 1e-4);
+}
+
+#[test]
+fn clipped_on_the_gpu()
+{
+    let (mut harness, mut texture) = scrolled(UiInit::new(common::font()));
+    let snapshot = harness.snapshot(&mut texture);
+    assert!(snapshot.lines().any(|line| line.starts_with("clip ")));
+    assert_eq!(images(&snapshot), ["image 0.00 0.00 80.00 40.00 (1.000 1.000 1.000 1.000)"]);
+}
+
+#[cfg(feature = "raster")]
+#[test]
+fn rasterized()
+{
+    use gru_ui::paint::raster::{RgbaImage, rasterize_textured};
+
+    let near = |a: [u8; 4], b: [u8; 4]| a.iter().zip(b).all(|(a, b)| a.abs_diff(b) <= 2);
+
+    //red left half, blue right half
+    let mut pixels = Vec::new();
+    for _ in 0..20 { for x in 0..40 { pixels.extend(if x < 20 { [255, 0, 0, 255] } else { [0, 0, 255, 255] }); } }
+    let image = RgbaImage { width: 40, height: 20, pixels };
+    for init in [UiInit::new(common::font()), UiInit::new(common::font()).cpu_clip()]
+    {
+        let (mut harness, mut texture) = scrolled(init);
+        let frame = harness.frame(&mut texture);
+        let raster = rasterize_textured(&frame.paint, 100, 60, &[(texture, &image)]);
+        assert!(near(raster.pixel(10, 15), [255, 0, 0, 255]));
+        assert!(near(raster.pixel(70, 15), [0, 0, 255, 255]));
+        //below the scroll view
+        assert_eq!(raster.pixel(10, 35), [0, 0, 0, 0]);
+    }
+}
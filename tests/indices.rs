@@ -0,0 +1,46 @@
+mod common;
+
+use gru_ui::{UiInit, math::Vec2, testing::Harness, widget::{WidgetExt, primitive::Label}};
+
+//4 vertices per glyph, more than u16 indices reach
+const GLYPHS: usize = 20000;
+
+//corner positions of every glyph quad in drawing order
+fn quads(init: UiInit) -> Vec<Vec<Vec2>>
+{
+    let mut harness: Harness<String, ()> = Harness::with_init(init, Vec2(100.0, 60.0), |_, _| Label::new().boxed());
+    let mut text = "x".repeat(GLYPHS);
+    let frame = harness.frame(&mut text);
+    let vertices = frame.paint.vertices;
+    assert!(vertices.len() > u16::MAX as usize);
+    let mut quads = Vec::new();
+    for command in frame.paint.commands
+    {
+        let indices = frame.paint.vertex_indices(command);
+        assert!(indices.iter().all(|i| (*i as usize) < vertices.len()));
+        quads.extend(indices.chunks_exact(6).map(|quad| quad.iter().map(|i| vertices[*i as usize].position).collect::<Vec<_>>()));
+    }
+    quads
+}
+
+fn left(quad: &[Vec2]) -> f32
+{
+    quad.iter().map(|pos| pos.0).fold(f32::INFINITY, f32::min)
+}
+
+#[test]
+fn narrow_matches_wide()
+{
+    let narrow = quads(UiInit::new(common::font()));
+    let wide = quads(UiInit::new(common::font()).wide_indices());
+    assert_eq!(narrow.len(), GLYPHS);
+    assert!(narrow == wide);
+    //monospaced glyphs each in their own slot, from left to right
+    let advance = left(&narrow[1]) - left(&narrow[0]);
+    assert!(advance > 0.0);
+    for (i, quad) in narrow.iter().enumerate()
+    {
+        let expected = left(&narrow[0]) + i as f32 * advance;
+        assert!((left(quad) - expected).abs() < advance / 4.0, "glyph {} at {} instead of {}", i, left(quad), expected);
+    }
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use gru_ui::{math::Vec2, event::{Event, GamepadButton, HardwareEvent, Key, LogicEvent, MouseButton}, state::WidgetId, testing::Harness, widget::{WidgetExt, layout::Empty, menu::{Menu, MenuEntry}}};
+
+//a focusable 4x4 unit area, a right click at (1, 1) opens the menu there with rows of one unit:
+//Cut, Copy (disabled), Paste and More, whose submenu with Undo and Redo opens right of the menu, from about 6.2 to 9.2 units
+fn harness() -> Harness<'static, (), &'static str>
+{
+    let mut harness = Harness::new(common::font(), Vec2(400.0, 200.0), |_, _|
+    {
+        let menu = Menu::new()
+            .action("Cut", "cut")
+            .entry(MenuEntry::new("Copy", "copy").enabled(false))
+            .action("Paste", "paste")
+            .submenu("More", Menu::new().action("Undo", "undo").action("Redo", "redo"));
+        Empty.fix().width(4.0).height(4.0).response().focusable(WidgetId::new("area")).context_menu(menu).align().left().up().boxed()
+    });
+    harness.frame(&mut ());
+    harness
+}
+
+fn open(harness: &mut Harness<(), &'static str>)
+{
+    harness.click(Vec2(20.0, 20.0), MouseButton::Secondary).frame(&mut ());
+}
+
+fn chosen(harness: &mut Harness<(), &'static str>) -> Vec<&'static str>
+{
+    harness.frame(&mut ()).events.iter().filter_map(|event| match event { Event::Logic(LogicEvent::Chosen(tag)) => Some(*tag), _ => None }).collect()
+}
+
+fn keys(harness: &mut Harness<(), &'static str>, keys: &[Key]) -> Vec<&'static str>
+{
+    for key in keys { harness.key(*key); }
+    chosen(harness)
+}
+
+fn button(harness: &mut Harness<(), &'static str>, button: GamepadButton) -> Vec<&'static str>
+{
+    harness.event(HardwareEvent::GamepadButton { button, pressed: true });
+    harness.event(HardwareEvent::GamepadButton { button, pressed: false });
+    chosen(harness)
+}
+
+#[test]
+fn right_click_and_choose()
+{
+    let mut harness = harness();
+    open(&mut harness);
+    harness.click(Vec2(40.0, 70.0), MouseButton::Primary);
+    assert_eq!(chosen(&mut harness), ["paste"]);
+    //choosing closes it
+    assert!(keys(&mut harness, &[Key::Down, Key::Return]).is_empty());
+}
+
+#[test]
+fn disabled_entry()
+{
+    let mut harness = harness();
+    open(&mut harness);
+    harness.click(Vec2(40.0, 50.0), MouseButton::Primary);
+    assert!(chosen(&mut harness).is_empty());
+    //still open with the disabled entry hovered
+    assert_eq!(keys(&mut harness, &[Key::Down, Key::Return]), ["paste"]);
+}
+
+#[test]
+fn arrow_keys()
+{
+    let mut harness = harness();
+    open(&mut harness);
+    //down from nothing starts at the top and skips the disabled entry
+    assert!(keys(&mut harness, &[Key::Down, Key::Down, Key::Up, Key::Up]).is_empty());
+    //up wrapped around to More, right opens it at its first entry and left closes it again
+    assert!(keys(&mut harness, &[Key::Right, Key::Down, Key::Left]).is_empty());
+    assert_eq!(keys(&mut harness, &[Key::Right, Key::Down, Key::Return]), ["redo"]);
+}
+
+#[test]
+fn escape()
+{
+    let mut harness = harness();
+    open(&mut harness);
+    //first only the submenu closes, More is still hovered and opens it again
+    assert!(keys(&mut harness, &[Key::Up, Key::Right, Key::Escape]).is_empty());
+    assert!(keys(&mut harness, &[Key::Return]).is_empty());
+    assert_eq!(keys(&mut harness, &[Key::Return]), ["undo"]);
+    //from the root it closes the whole menu
+    open(&mut harness);
+    assert!(keys(&mut harness, &[Key::Down, Key::Escape]).is_empty());
+    assert!(keys(&mut harness, &[Key::Down, Key::Return]).is_empty());
+}
+
+#[test]
+fn gamepad()
+{
+    let mut harness = harness();
+    //the menu button only opens it while the focus is inside
+    assert!(button(&mut harness, GamepadButton::Menu).is_empty());
+    assert!(button(&mut harness, GamepadButton::South).is_empty());
+    harness.ui().set_focus(Some(WidgetId::new("area")));
+    harness.frame(&mut ());
+    //it opens with the first entry hovered
+    button(&mut harness, GamepadButton::Menu);
+    assert_eq!(button(&mut harness, GamepadButton::South), ["cut"]);
+    button(&mut harness, GamepadButton::Menu);
+    button(&mut harness, GamepadButton::DPadDown);
+    assert_eq!(button(&mut harness, GamepadButton::South), ["paste"]);
+    button(&mut harness, GamepadButton::Menu);
+    button(&mut harness, GamepadButton::East);
+    assert!(button(&mut harness, GamepadButton::DPadDown).is_empty());
+    assert!(button(&mut harness, GamepadButton::South).is_empty());
+}
+
+#[test]
+fn stale_submenu_panel()
+{
+    //hovering More opens the submenu, which is painted
+    let mut harness = harness();
+    open(&mut harness);
+    harness.pointer_move(Vec2(40.0, 90.0)).frame(&mut ());
+    //within one frame the pointer closes it again and moves to where it was
+    harness.pointer_move(Vec2(40.0, 30.0)).pointer_move(Vec2(150.0, 90.0)).pointer_move(Vec2(150.0, 110.0));
+    assert!(chosen(&mut harness).is_empty());
+    //back on More the submenu works as before
+    harness.pointer_move(Vec2(40.0, 90.0)).frame(&mut ());
+    harness.pointer_move(Vec2(150.0, 110.0)).frame(&mut ());
+    harness.click(Vec2(150.0, 110.0), MouseButton::Primary);
+    assert_eq!(chosen(&mut harness), ["redo"]);
+}
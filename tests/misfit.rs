@@ -0,0 +1,38 @@
+mod common;
+
+use gru_ui::{Axis, math::Vec2, testing::Harness, widget::{WidgetExt, primitive::Label, layout::{Flex, Empty}}};
+
+#[test]
+fn nested_label()
+{
+    //the first label has the whole width, the second one only a unit
+    let mut harness: Harness<&'static str, ()> = Harness::new(common::font(), Vec2(200.0, 100.0), |_, _| Flex::column().with(Label::new()).with(Label::new().fix().width(1.0)).boxed());
+    let frame = harness.frame(&mut "wide");
+    assert!(!frame.fits);
+    assert_eq!(frame.misfits.len(), 1);
+    let misfit = &frame.misfits[0];
+    assert_eq!((misfit.path.as_str(), misfit.name), ("Column[1]", "Label"));
+    assert!(misfit.axis == Axis::Horizontal);
+    assert!(misfit.requested.0 > 2.0 && misfit.available.0 == 1.0);
+}
+
+#[test]
+fn root_reports_once()
+{
+    let mut harness: Harness<&'static str, ()> = Harness::new(common::font(), Vec2(20.0, 10.0), |_, _| Label::new().boxed());
+    let frame = harness.frame(&mut "wide");
+    assert_eq!(frame.misfits.len(), 1);
+    assert_eq!((frame.misfits[0].path.as_str(), frame.misfits[0].name), ("", "Label"));
+    assert!(frame.misfits[0].axis == Axis::Both);
+}
+
+#[test]
+fn root_without_a_report()
+{
+    //a fixed size does not report itself, so the ui does
+    let mut harness: Harness<(), ()> = Harness::new(common::font(), Vec2(20.0, 100.0), |_, _| Empty.fix().width(2.0).height(1.0).boxed());
+    let frame = harness.frame(&mut ());
+    assert_eq!(frame.misfits.len(), 1);
+    assert_eq!(frame.misfits[0].name, "Ui");
+    assert!(frame.misfits[0].axis == Axis::Horizontal);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use gru_ui::{math::Vec2, event::{HardwareEvent, Key, MouseButton}, testing::Harness, widget::{WidgetExt, primitive::Edit}};
+
+//an edit that was clicked, so it takes the typed characters unless ctrl is held
+fn edit() -> (Harness<'static, String, ()>, String)
+{
+    let mut harness = Harness::new(common::font(), Vec2(200.0, 100.0), |_, _| Edit::new().response().boxed());
+    let mut data = String::new();
+    harness.frame(&mut data);
+    harness.click(Vec2(10.0, 10.0), MouseButton::Primary).frame(&mut data);
+    (harness, data)
+}
+
+fn press(harness: &mut Harness<String, ()>, key: Key, pressed: bool)
+{
+    harness.event(HardwareEvent::Key { key, pressed });
+}
+
+#[test]
+fn left_and_right_apart()
+{
+    let (mut harness, mut data) = edit();
+    press(&mut harness, Key::LControl, true);
+    press(&mut harness, Key::RControl, true);
+    press(&mut harness, Key::LControl, false);
+    harness.text("a").frame(&mut data);
+    assert_eq!(data, "");
+    press(&mut harness, Key::RControl, false);
+    harness.text("b").frame(&mut data);
+    assert_eq!(data, "b");
+}
+
+#[test]
+fn released_when_the_window_loses_the_input()
+{
+    let (mut harness, mut data) = edit();
+    //the release of ctrl never arrives
+    press(&mut harness, Key::LControl, true);
+    harness.event(HardwareEvent::PointerGone).frame(&mut data);
+    harness.pointer_move(Vec2(10.0, 10.0)).text("a").frame(&mut data);
+    assert_eq!(data, "a");
+}
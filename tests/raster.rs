@@ -0,0 +1,43 @@
+#![cfg(feature = "raster")]
+
+mod common;
+
+use gru_ui::{math::Vec2, paint::{TexCoords, raster::{RgbaImage, rasterize}}, testing::Harness, widget::{WidgetExt, primitive::Label}};
+
+//one glyph two units high, with the pixel bounds of its quad
+fn glyph(text: &'static str) -> (RgbaImage, Vec2, Vec2)
+{
+    let mut harness: Harness<&'static str, ()> = Harness::new(common::font(), Vec2(100.0, 60.0), |_, _| Label::new().size(2.0).boxed());
+    let mut data = text;
+    let frame = harness.frame(&mut data);
+    let corners: Vec<Vec2> = frame.paint.vertices.iter().filter(|vertex| matches!(vertex.tex_coords, TexCoords::Glyph(..))).map(|vertex| vertex.position).collect();
+    assert_eq!(corners.len(), 4);
+    let min = corners.iter().fold(Vec2(f32::INFINITY, f32::INFINITY), |min, pos| Vec2(min.0.min(pos.0), min.1.min(pos.1)));
+    let max = corners.iter().fold(Vec2(f32::NEG_INFINITY, f32::NEG_INFINITY), |max, pos| Vec2(max.0.max(pos.0), max.1.max(pos.1)));
+    (rasterize(&frame.paint, 100, 60), min, max)
+}
+
+fn alpha(image: &RgbaImage, pos: Vec2) -> u8
+{
+    image.pixel(pos.0 as u32, pos.1 as u32)[3]
+}
+
+#[test]
+fn inside_a_glyph()
+{
+    let (image, min, max) = glyph("█");
+    assert_eq!(image.pixel(((min.0 + max.0) / 2.0) as u32, ((min.1 + max.1) / 2.0) as u32), [0, 0, 0, 255]);
+    //nothing drawn next to the glyph
+    assert_eq!(alpha(&image, Vec2(max.0 + 5.0, (min.1 + max.1) / 2.0)), 0);
+}
+
+#[test]
+fn outside_a_glyph()
+{
+    //the hole of the o lies inside its quad, but outside the glyph
+    let (image, min, max) = glyph("o");
+    let center = (min + max) / 2.0;
+    assert_eq!(alpha(&image, center), 0);
+    //while the ring around it is covered on the same row
+    assert!((min.0 as u32..max.0 as u32).any(|x| image.pixel(x, center.1 as u32)[3] == 255));
+}
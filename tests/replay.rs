@@ -0,0 +1,45 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use gru_ui::{math::Vec2, event::{Event, LogicEvent, MouseButton}, replay::Recording, testing::Harness, widget::{WidgetExt, primitive::Label}};
+
+fn harness() -> Harness<'static, u32, u8>
+{
+    Harness::new(common::font(), Vec2(100.0, 40.0), |_, _| Label::new().map(|count: &u32| count.to_string()).response().event(0).action(|_, count| *count += 1).boxed())
+}
+
+fn clicks(events: &[Event<u8>]) -> usize
+{
+    events.iter().filter(|event| matches!(event, Event::Logic(LogicEvent::Clicked(0, MouseButton::Primary)))).count()
+}
+
+#[test]
+fn round_trip()
+{
+    let mut recorded = harness();
+    let mut count = 0;
+    let mut recorded_clicks = 0;
+    recorded.ui().start_recording();
+    recorded.frame(&mut count);
+    for _ in 0..2
+    {
+        recorded.click(Vec2(5.0, 5.0), MouseButton::Primary);
+        recorded_clicks += clicks(recorded.frame(&mut count).events);
+    }
+    //misses the label
+    recorded.click(Vec2(90.0, 30.0), MouseButton::Primary);
+    recorded_clicks += clicks(recorded.frame(&mut count).events);
+    assert_eq!((count, recorded_clicks), (2, 2));
+
+    let recording = recorded.ui().stop_recording().unwrap();
+    let json = serde_json::to_string(&recording).unwrap();
+    let recording: Recording = serde_json::from_str(&json).unwrap();
+    assert_eq!(recording.frames.len(), 4);
+
+    let mut replayed = harness();
+    let mut replayed_count = 0;
+    let mut replayed_clicks = 0;
+    recording.replay(replayed.ui(), &mut replayed_count, |frame| replayed_clicks += clicks(frame.events));
+    assert_eq!((replayed_count, replayed_clicks), (count, recorded_clicks));
+}
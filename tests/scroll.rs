@@ -0,0 +1,82 @@
+mod common;
+
+use gru_ui::{math::Vec2, event::{HardwareEvent, MouseButton}, state::Persistent, testing::Harness, widget::{WidgetExt, primitive::Label, layout::{Flex, Empty}}};
+
+//a column of 20 rows in a view of 5x5 units, the vertical bar takes the right half unit
+fn rows(offset: Persistent<Vec2>) -> Harness<'static, &'static str, ()>
+{
+    let mut harness = Harness::new(common::font(), Vec2(100.0, 100.0), move |_, _|
+    {
+        let mut column = Flex::column();
+        for _ in 0..20 { column = column.with(Label::new()); }
+        column.scroll().persist(offset.clone()).boxed()
+    });
+    harness.frame(&mut "row");
+    harness
+}
+
+fn close(a: Vec2, b: Vec2) -> bool
+{
+    (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4
+}
+
+#[test]
+fn min_size()
+{
+    //along the scrolling axis one thumb is enough, across it the content and the bar
+    let mut vertical: Harness<(), ()> = Harness::new(common::font(), Vec2(50.0, 20.0), |_, _| Empty.fix().width(2.0).height(10.0).scroll().boxed());
+    assert!(vertical.frame(&mut ()).fits);
+    let mut vertical: Harness<(), ()> = Harness::new(common::font(), Vec2(49.0, 20.0), |_, _| Empty.fix().width(2.0).height(10.0).scroll().boxed());
+    assert!(!vertical.frame(&mut ()).fits);
+    let mut horizontal: Harness<(), ()> = Harness::new(common::font(), Vec2(20.0, 30.0), |_, _| Empty.fix().width(10.0).height(1.0).scroll().horizontal(true).vertical(false).boxed());
+    assert!(horizontal.frame(&mut ()).fits);
+    let mut horizontal: Harness<(), ()> = Harness::new(common::font(), Vec2(20.0, 29.0), |_, _| Empty.fix().width(10.0).height(1.0).scroll().horizontal(true).vertical(false).boxed());
+    assert!(!horizontal.frame(&mut ()).fits);
+}
+
+#[test]
+fn wheel()
+{
+    let offset = Persistent::new(Vec2::zero());
+    let mut harness = rows(offset.clone());
+    harness.event(HardwareEvent::Scroll { pos: Vec2(50.0, 50.0), delta: Vec2(0.0, -3.0) }).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 3.0)));
+    //a sideways wheel does nothing to a vertical scroll
+    harness.event(HardwareEvent::Scroll { pos: Vec2(50.0, 50.0), delta: Vec2(-2.0, 0.0) }).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 3.0)));
+    //stops at the end of the content
+    harness.event(HardwareEvent::Scroll { pos: Vec2(50.0, 50.0), delta: Vec2(0.0, -100.0) }).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 15.0)));
+    harness.event(HardwareEvent::Scroll { pos: Vec2(50.0, 50.0), delta: Vec2(0.0, 100.0) }).frame(&mut "row");
+    assert!(close(offset.get(), Vec2::zero()));
+}
+
+#[test]
+fn drag_thumb()
+{
+    //the thumb is 1.25 units long with 3.75 units of track left for 15 units of content
+    let offset = Persistent::new(Vec2::zero());
+    let mut harness = rows(offset.clone());
+    harness.pointer_move(Vec2(95.0, 10.0)).event(HardwareEvent::PointerClicked { pos: Vec2(95.0, 10.0), button: MouseButton::Primary, pressed: true });
+    harness.frame(&mut "row");
+    assert!(close(offset.get(), Vec2::zero()));
+    //the thumb keeps its place under the pointer
+    harness.pointer_move(Vec2(95.0, 30.0)).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 4.0)));
+    //even when the pointer leaves the bar
+    harness.pointer_move(Vec2(20.0, 99.0)).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 15.0)));
+    harness.event(HardwareEvent::PointerClicked { pos: Vec2(20.0, 99.0), button: MouseButton::Primary, pressed: false });
+    harness.pointer_move(Vec2(95.0, 10.0)).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 15.0)));
+}
+
+#[test]
+fn click_track()
+{
+    //the thumb jumps to be centered on the pointer
+    let offset = Persistent::new(Vec2::zero());
+    let mut harness = rows(offset.clone());
+    harness.click(Vec2(95.0, 80.0), MouseButton::Primary).frame(&mut "row");
+    assert!(close(offset.get(), Vec2(0.0, 13.5)));
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use gru_ui::{Widget, math::Vec2, event::{Event, Key, KeyChord, LogicEvent}, state::WidgetId, testing::Harness, widget::{WidgetExt, layout::{Flex, Empty}}};
+
+fn cell(name: &'static str) -> impl Widget<(), &'static str>
+{
+    Empty.fix().width(1.0).height(1.0).response().focusable(WidgetId::new(name))
+}
+
+//the same chord in an inner scope, the outer scope around it and globally, with a focusable outside of both
+fn scopes() -> Harness<'static, (), &'static str>
+{
+    let save = KeyChord::new(Key::S).ctrl();
+    let mut harness = Harness::new(common::font(), Vec2(100.0, 100.0), move |_, _|
+    {
+        let outer = Flex::column().with(cell("inner").shortcut(save, "inner")).with(cell("outer")).shortcut(save, "outer");
+        Flex::column().with(outer).with(cell("other")).boxed()
+    });
+    harness.ui().shortcut(save, "global");
+    harness.frame(&mut ());
+    harness
+}
+
+fn fired(harness: &mut Harness<(), &'static str>, focus: Option<&'static str>, chord: KeyChord) -> Vec<&'static str>
+{
+    harness.ui().set_focus(focus.map(WidgetId::new));
+    harness.frame(&mut ());
+    harness.chord(chord);
+    harness.frame(&mut ()).events.iter().filter_map(|event| match event { Event::Logic(LogicEvent::Shortcut(tag)) => Some(*tag), _ => None }).collect()
+}
+
+#[test]
+fn inner_before_outer_before_global()
+{
+    let save = KeyChord::new(Key::S).ctrl();
+    let mut harness = scopes();
+    //without a focus every scope listens and the innermost one wins
+    assert_eq!(fired(&mut harness, None, save), ["inner"]);
+    assert_eq!(fired(&mut harness, Some("inner"), save), ["inner"]);
+    assert_eq!(fired(&mut harness, Some("outer"), save), ["outer"]);
+    assert_eq!(fired(&mut harness, Some("other"), save), ["global"]);
+}
+
+#[test]
+fn modifiers_match_exactly()
+{
+    let mut harness = scopes();
+    assert!(fired(&mut harness, None, KeyChord::new(Key::S).ctrl().shift()).is_empty());
+    assert!(fired(&mut harness, Some("other"), KeyChord::new(Key::S).ctrl().shift()).is_empty());
+    assert!(fired(&mut harness, None, KeyChord::new(Key::S)).is_empty());
+}
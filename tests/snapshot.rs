@@ -0,0 +1,48 @@
+mod common;
+
+use gru_ui::{math::Vec2, testing::{Harness, assert_snapshot}, widget::{WidgetExt, primitive::Label, layout::{Flex, Empty}}};
+
+//glyph quads depend on the sdf atlas, so text only keeps its glyph count and color
+fn without_glyph_bounds(snapshot: &str) -> String
+{
+    let mut stripped = String::new();
+    for line in snapshot.lines()
+    {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.first() == Some(&"text") { stripped.push_str(&format!("text {}\n", words[5..].join(" "))); }
+        else { stripped.push_str(&format!("{}\n", line)); }
+    }
+    stripped
+}
+
+#[test]
+fn padded_labels()
+{
+    let mut harness: Harness<&'static str, ()> = Harness::new(common::font(), Vec2(200.0, 120.0), |_, _|
+    {
+        Flex::column().padding(0.5)
+            .with(Label::new().fix().width(4.0).bg().pad().left(1.0).up(0.5))
+            .with(Label::new().fix().width(3.0).bg().align().center_h())
+            .with(Flex::row().padding(1.0).with(Label::new().fix().width(2.0).bg()).with(Label::new().fix().width(2.0).bg()))
+            .boxed()
+    });
+    let snapshot = harness.snapshot(&mut "ok");
+    assert_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/padded_labels.txt"), &without_glyph_bounds(&snapshot));
+}
+
+#[cfg(feature = "raster")]
+#[test]
+fn padded_rects()
+{
+    use gru_ui::testing::assert_image_snapshot;
+
+    let mut harness: Harness<(), ()> = Harness::new(common::font(), Vec2(100.0, 60.0), |_, _|
+    {
+        Flex::column().padding(0.5)
+            .with(Empty.fix().width(2.0).height(1.0).bg().pad().left(1.0).up(0.5))
+            .with(Empty.fix().width(1.0).height(1.0).bg().align().center_h())
+            .boxed()
+    });
+    let image = harness.image(&mut ());
+    assert_image_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/padded_rects.png"), &image);
+}
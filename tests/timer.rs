@@ -0,0 +1,90 @@
+mod common;
+
+use gru_ui::{Widget, EventCtx, LayoutInquireCtx, LayoutComputeCtx, PaintCtx, math::Vec2, event::{HardwareEvent, Key, TouchPhase, WidgetEvent}, testing::Harness, timer::TimerId};
+use std::{cell::Cell, rc::Rc};
+
+//A schedules a timer a second from now, B cancels it
+struct Clock
+{
+    timer: Option<TimerId>,
+    fired: Rc<Cell<u32>>,
+}
+
+impl Widget<(), ()> for Clock
+{
+    fn event(&mut self, ctx: &mut EventCtx<()>, _: &mut ())
+    {
+        match &ctx.event
+        {
+            WidgetEvent::Timer(id) => if self.timer == Some(*id)
+            {
+                self.timer = None;
+                self.fired.set(self.fired.get() + 1);
+            },
+            WidgetEvent::Hardware(event) => match event.event
+            {
+                HardwareEvent::Key { key: Key::A, pressed: true } => self.timer = Some(ctx.schedule(1.0)),
+                HardwareEvent::Key { key: Key::B, pressed: true } => if let Some(id) = self.timer.take() { ctx.cancel(id); },
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+
+    fn layout_inquire(&mut self, _: &mut LayoutInquireCtx, _: &()) -> Vec2 { Vec2(1.0, 1.0) }
+    fn layout_compute(&mut self, _: &mut LayoutComputeCtx, _: &(), _: Vec2) -> Vec2 { Vec2(1.0, 1.0) }
+    fn paint(&mut self, _: &mut PaintCtx, _: &()) {}
+}
+
+//frames are a quarter second apart
+fn clock(fired: Rc<Cell<u32>>) -> Harness<'static, (), ()>
+{
+    let mut harness = Harness::new(common::font(), Vec2(100.0, 100.0), move |_, _| Box::new(Clock { timer: None, fired: fired.clone() }));
+    *harness.dt() = 0.25;
+    harness.frame(&mut ());
+    harness
+}
+
+#[test]
+fn fires_once()
+{
+    let fired = Rc::new(Cell::new(0));
+    let mut harness = clock(fired.clone());
+    harness.key(Key::A).frame(&mut ());
+    for _ in 0..3 { harness.frame(&mut ()); }
+    assert_eq!(fired.get(), 0);
+    harness.frame(&mut ());
+    assert_eq!(fired.get(), 1);
+    for _ in 0..8 { harness.frame(&mut ()); }
+    assert_eq!(fired.get(), 1);
+}
+
+#[test]
+fn cancel()
+{
+    let fired = Rc::new(Cell::new(0));
+    let mut harness = clock(fired.clone());
+    harness.key(Key::A).frame(&mut ());
+    harness.frame(&mut ());
+    harness.key(Key::B).frame(&mut ());
+    for _ in 0..8 { harness.frame(&mut ()); }
+    assert_eq!(fired.get(), 0);
+    assert_eq!(harness.frame(&mut ()).next_deadline, None);
+}
+
+#[test]
+fn next_deadline()
+{
+    let mut harness = clock(Rc::default());
+    assert_eq!(harness.frame(&mut ()).next_deadline, None);
+    //counted from the current frame
+    assert_eq!(harness.key(Key::A).frame(&mut ()).next_deadline, Some(1.0));
+    assert_eq!(harness.frame(&mut ()).next_deadline, Some(0.75));
+    harness.key(Key::B).frame(&mut ());
+    //a resting finger may become a long press before the timer runs out
+    harness.key(Key::A).touch(0, TouchPhase::Start, Vec2(10.0, 10.0));
+    let long_press = harness.frame(&mut ()).next_deadline.unwrap();
+    assert!((long_press - 0.6).abs() < 1e-4);
+    harness.touch(0, TouchPhase::End, Vec2(10.0, 10.0));
+    assert_eq!(harness.frame(&mut ()).next_deadline, Some(0.75));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use gru_ui::{math::Vec2, event::MouseButton, testing::Harness, widget::{WidgetExt, layout::Empty}};
+
+//a 2x2 unit square with a 1x1 tooltip, frames a quarter second apart
+fn harness() -> Harness<'static, (), ()>
+{
+    let mut harness = Harness::new(common::font(), Vec2(200.0, 100.0), |_, _| Empty.fix().width(2.0).height(2.0).bg().tooltip(Empty.fix().width(1.0).height(1.0).bg()).align().left().up().boxed());
+    *harness.dt() = 0.25;
+    harness.frame(&mut ());
+    harness
+}
+
+//whether the tooltip is painted on top of the square, each frame paints a snapshot
+fn open(harness: &mut Harness<(), ()>) -> bool
+{
+    harness.snapshot(&mut ()).lines().filter(|line| line.starts_with("rect")).count() == 2
+}
+
+#[test]
+fn delay()
+{
+    let mut harness = harness();
+    harness.pointer_move(Vec2(10.0, 10.0));
+    assert!(!open(&mut harness));
+    //moving on the square starts the wait over
+    harness.pointer_move(Vec2(20.0, 20.0));
+    assert!(!open(&mut harness));
+    assert!(!open(&mut harness));
+    assert!(open(&mut harness));
+    assert!(open(&mut harness));
+}
+
+#[test]
+fn closed_by_leaving()
+{
+    let mut harness = harness();
+    harness.pointer_move(Vec2(10.0, 10.0));
+    for _ in 0..2 { open(&mut harness); }
+    assert!(open(&mut harness));
+    harness.pointer_move(Vec2(150.0, 80.0));
+    assert!(!open(&mut harness));
+    //and it stays closed
+    for _ in 0..4 { assert!(!open(&mut harness)); }
+}
+
+#[test]
+fn closed_by_clicking()
+{
+    let mut harness = harness();
+    harness.pointer_move(Vec2(10.0, 10.0));
+    for _ in 0..2 { open(&mut harness); }
+    assert!(open(&mut harness));
+    harness.click(Vec2(10.0, 10.0), MouseButton::Primary);
+    assert!(!open(&mut harness));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use gru_ui::{math::Vec2, event::{Event, TouchPhase}, gesture::Gesture, state::Persistent, testing::Harness, widget::{WidgetExt, primitive::Label, layout::Flex}};
+
+fn harness() -> Harness<'static, &'static str, ()>
+{
+    Harness::new(common::font(), Vec2(100.0, 100.0), |_, _| Label::new().boxed())
+}
+
+fn gestures(events: &[Event<()>]) -> Vec<Gesture>
+{
+    events.iter().filter_map(|event| match event { Event::Gesture(gesture) => Some(*gesture), _ => None }).collect()
+}
+
+//a column of 20 rows in a view of 5x5 units
+fn scroll_harness(offset: Persistent<Vec2>) -> Harness<'static, &'static str, ()>
+{
+    Harness::new(common::font(), Vec2(100.0, 100.0), move |_, _|
+    {
+        let mut column = Flex::column();
+        for _ in 0..20 { column = column.with(Label::new()); }
+        column.scroll().persist(offset.clone()).boxed()
+    })
+}
+
+#[test]
+fn tap()
+{
+    let mut harness = harness();
+    let mut data = "tap";
+    harness.touch(0, TouchPhase::Start, Vec2(10.0, 10.0));
+    assert!(gestures(harness.frame(&mut data).events).is_empty());
+    harness.touch(0, TouchPhase::End, Vec2(12.0, 10.0));
+    assert!(gestures(harness.frame(&mut data).events) == [Gesture::Tap { pos: Vec2(0.6, 0.5) }]);
+}
+
+#[test]
+fn long_press()
+{
+    let mut harness = harness();
+    let mut data = "long press";
+    harness.touch(0, TouchPhase::Start, Vec2(10.0, 10.0));
+    assert!(gestures(harness.frame(&mut data).events).is_empty());
+    *harness.dt() = 0.7;
+    assert!(gestures(harness.frame(&mut data).events) == [Gesture::LongPress { pos: Vec2(0.5, 0.5) }]);
+    //no tap after the long press
+    harness.touch(0, TouchPhase::End, Vec2(10.0, 10.0));
+    assert!(gestures(harness.frame(&mut data).events).is_empty());
+}
+
+#[test]
+fn swipe()
+{
+    let mut harness = harness();
+    let mut data = "swipe";
+    harness.touch(0, TouchPhase::Start, Vec2(10.0, 10.0));
+    harness.frame(&mut data);
+    harness.touch(0, TouchPhase::Move, Vec2(50.0, 10.0)).touch(0, TouchPhase::Move, Vec2(90.0, 10.0)).touch(0, TouchPhase::End, Vec2(90.0, 10.0));
+    assert!(gestures(harness.frame(&mut data).events) == [Gesture::Swipe { start: Vec2(0.5, 0.5), delta: Vec2(4.0, 0.0) }]);
+}
+
+#[test]
+fn pinch()
+{
+    let mut harness = harness();
+    let mut data = "pinch";
+    harness.touch(0, TouchPhase::Start, Vec2(20.0, 20.0)).touch(1, TouchPhase::Start, Vec2(60.0, 20.0));
+    harness.frame(&mut data);
+    harness.touch(1, TouchPhase::Move, Vec2(100.0, 20.0));
+    assert!(gestures(harness.frame(&mut data).events) == [Gesture::Pinch { center: Vec2(3.0, 1.0), scale: 2.0 }]);
+    //no tap or swipe once two fingers were down
+    harness.touch(1, TouchPhase::End, Vec2(100.0, 20.0)).touch(0, TouchPhase::End, Vec2(20.0, 20.0));
+    assert!(gestures(harness.frame(&mut data).events).is_empty());
+}
+
+#[test]
+fn scroll_pan()
+{
+    let offset = Persistent::new(Vec2::zero());
+    let mut harness = scroll_harness(offset.clone());
+    let mut data = "row";
+    harness.frame(&mut data);
+    harness.touch(0, TouchPhase::Start, Vec2(50.0, 80.0));
+    harness.frame(&mut data);
+    //below the threshold nothing moves
+    harness.touch(0, TouchPhase::Move, Vec2(50.0, 75.0));
+    harness.frame(&mut data);
+    assert!(offset.get() == Vec2::zero());
+    harness.touch(0, TouchPhase::Move, Vec2(50.0, 40.0));
+    harness.frame(&mut data);
+    assert!(offset.get() == Vec2(0.0, 1.75));
+    harness.touch(0, TouchPhase::End, Vec2(50.0, 40.0));
+    harness.frame(&mut data);
+    assert!(offset.get() == Vec2(0.0, 1.75));
+}
+
+#[test]
+fn scroll_second_finger()
+{
+    let offset = Persistent::new(Vec2::zero());
+    let mut harness = scroll_harness(offset.clone());
+    let mut data = "row";
+    harness.frame(&mut data);
+    harness.touch(0, TouchPhase::Start, Vec2(50.0, 80.0)).touch(0, TouchPhase::Move, Vec2(50.0, 40.0));
+    harness.frame(&mut data);
+    let panned = offset.get();
+    assert!(panned != Vec2::zero());
+    //the second finger does not take over the pan
+    harness.touch(1, TouchPhase::Start, Vec2(50.0, 80.0)).touch(1, TouchPhase::Move, Vec2(50.0, 20.0));
+    harness.frame(&mut data);
+    assert!(offset.get() == panned);
+    //but the first one keeps panning
+    harness.touch(0, TouchPhase::Move, Vec2(50.0, 20.0));
+    harness.frame(&mut data);
+    assert!(offset.get() == panned + Vec2(0.0, 1.0));
+    //once both are up a new finger pans again
+    harness.touch(0, TouchPhase::End, Vec2(50.0, 20.0)).touch(1, TouchPhase::End, Vec2(50.0, 20.0));
+    harness.frame(&mut data);
+    harness.touch(2, TouchPhase::Start, Vec2(50.0, 80.0)).touch(2, TouchPhase::Move, Vec2(50.0, 60.0));
+    harness.frame(&mut data);
+    assert!(offset.get() == panned + Vec2(0.0, 2.0));
+}